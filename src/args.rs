@@ -0,0 +1,76 @@
+//! Command-line arguments for configuring a signer with `clap`.
+//!
+//! [`SignerArgs`] can be flattened into a CLI's own arguments:
+//!
+//! ```rust,no_run
+//! #[derive(clap::Parser)]
+//! struct Cli {
+//!     #[command(flatten)]
+//!     signer: aws_rds_signer::SignerArgs,
+//! }
+//! ```
+
+use clap::Args;
+
+use crate::sign::{Signer, SignerBuilder};
+
+/// Command-line arguments describing the database to sign tokens for.
+///
+/// The host, port, user, region, and expires-in arguments can also be set
+/// through the `RDS_SIGNER_*` environment variables read by [`Signer::from_env`].
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub struct SignerArgs {
+    /// The RDS instance hostname
+    #[arg(long, env = "RDS_SIGNER_HOST")]
+    pub host: String,
+    /// The database port [default: 5432]
+    #[arg(long, env = "RDS_SIGNER_PORT")]
+    pub port: Option<u16>,
+    /// The database user to authenticate as
+    #[arg(long, env = "RDS_SIGNER_USER")]
+    pub user: String,
+    /// The AWS region, inferred from the hostname or the AWS configuration if unset
+    #[arg(long, env = "RDS_SIGNER_REGION")]
+    pub region: Option<String>,
+    /// The token lifetime in seconds [default: 900]
+    #[arg(long, env = "RDS_SIGNER_EXPIRES_IN", value_name = "SECONDS")]
+    pub expires_in: Option<u64>,
+    /// The AWS profile used for credentials and region
+    #[arg(long)]
+    pub profile: Option<String>,
+    /// An IAM role to assume before signing
+    #[arg(long)]
+    pub role_arn: Option<String>,
+}
+
+impl SignerArgs {
+    /// Returns a builder configured with these arguments.
+    #[must_use]
+    pub fn builder(&self) -> SignerBuilder {
+        let mut builder = Signer::builder().host(&self.host).user(&self.user);
+        if let Some(port) = self.port {
+            builder = builder.port(port);
+        }
+        if let Some(region) = &self.region {
+            builder = builder.region(region);
+        }
+        if let Some(expires_in) = self.expires_in {
+            builder = builder.expires_in_secs(expires_in);
+        }
+        if let Some(profile) = &self.profile {
+            builder = builder.profile(profile);
+        }
+        if let Some(role_arn) = &self.role_arn {
+            builder = builder.assume_role(role_arn.as_str());
+        }
+        builder
+    }
+
+    /// Creates a signer from these arguments, validated with [`SignerBuilder::try_build`].
+    ///
+    /// # Errors
+    /// * `ValidationError` - If the arguments are invalid
+    pub fn into_signer(self) -> Result<Signer, crate::Error> {
+        self.builder().try_build()
+    }
+}
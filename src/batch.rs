@@ -0,0 +1,64 @@
+//! Token generation for several endpoints at once.
+
+use std::collections::HashMap;
+
+use crate::sign::{AuthToken, Signer};
+
+/// A database endpoint to sign a token for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Endpoint {
+    /// The database hostname.
+    pub host: String,
+    /// The database port.
+    pub port: u16,
+}
+
+impl Endpoint {
+    /// Creates an endpoint for `host` and `port`.
+    ///
+    /// # Arguments
+    /// * `host` - The database hostname (e.g., "mydb.xxxx.us-east-1.rds.amazonaws.com")
+    /// * `port` - The database port
+    #[must_use]
+    pub fn new(host: impl Into<String>, port: u16) -> Self {
+        Self {
+            host: host.into(),
+            port,
+        }
+    }
+}
+
+impl Signer {
+    /// Generates a token for each of `endpoints`, keyed by endpoint.
+    ///
+    /// Every token is signed for the signer's user with the same credentials,
+    /// which are resolved at most once and cached as for [`Self::fetch_token`].
+    /// Signing itself is local and does no I/O, so the tokens are signed one
+    /// after another once the credentials are available. Each token is signed
+    /// for the region of its endpoint's hostname, or the signer's region if the
+    /// hostname names none. Use it for a writer and several readers, or for
+    /// many tenant databases at startup.
+    ///
+    /// # Arguments
+    /// * `endpoints` - The endpoints to sign tokens for
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::fetch_token`].
+    pub async fn fetch_tokens_for(
+        &self,
+        endpoints: &[Endpoint],
+    ) -> Result<HashMap<Endpoint, AuthToken>, crate::Error> {
+        if endpoints.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let (credentials, region) = self.resolve_for_signing().await?;
+        let mut tokens = HashMap::with_capacity(endpoints.len());
+        for endpoint in endpoints {
+            let signer = self.with_endpoint(endpoint.host.as_str(), endpoint.port);
+            let region = signer.explicit_region().unwrap_or_else(|| region.clone());
+            let token = signer.sign_token(credentials.clone(), &region)?;
+            tokens.insert(endpoint.clone(), token);
+        }
+        Ok(tokens)
+    }
+}
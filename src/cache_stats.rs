@@ -0,0 +1,102 @@
+//! Counters of token cache activity.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{PoisonError, RwLock};
+use std::time::SystemTime;
+
+/// A snapshot of a token cache's activity since it was created.
+///
+/// Returned by [`TokenCache::stats`](crate::TokenCache::stats) and
+/// [`CachingSigner::stats`](crate::CachingSigner::stats). Poll it to export
+/// hit ratios and refresh failures to dashboards; the counters only grow, so
+/// rates are the difference between two snapshots.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Requests answered with a cached token.
+    pub hits: u64,
+    /// Requests that found no usable cached token and minted one.
+    pub misses: u64,
+    /// Tokens minted successfully.
+    pub refreshes: u64,
+    /// Failed attempts to mint a token.
+    pub refresh_failures: u64,
+    /// Requests answered with an unexpired cached token after a failed refresh.
+    pub stale_hits: u64,
+    /// When the last token was minted.
+    pub last_refresh: Option<SystemTime>,
+    /// When the last attempt to mint a token failed.
+    pub last_failure: Option<SystemTime>,
+}
+
+/// The live counters behind [`CacheStats`].
+#[derive(Debug, Default)]
+pub(crate) struct StatsRecorder {
+    /// Requests answered with a cached token.
+    hits: AtomicU64,
+    /// Requests that minted a token.
+    misses: AtomicU64,
+    /// Tokens minted successfully.
+    refreshes: AtomicU64,
+    /// Failed attempts to mint a token.
+    refresh_failures: AtomicU64,
+    /// Requests answered with a stale token.
+    stale_hits: AtomicU64,
+    /// When the last token was minted.
+    last_refresh: RwLock<Option<SystemTime>>,
+    /// When the last attempt to mint a token failed.
+    last_failure: RwLock<Option<SystemTime>>,
+}
+
+impl StatsRecorder {
+    /// Records a request answered with a cached token.
+    pub(crate) fn hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a request that has to mint a token.
+    pub(crate) fn miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a minted token.
+    pub(crate) fn refreshed(&self) {
+        self.refreshes.fetch_add(1, Ordering::Relaxed);
+        *self
+            .last_refresh
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(SystemTime::now());
+    }
+
+    /// Records a failed attempt to mint a token.
+    pub(crate) fn refresh_failed(&self) {
+        self.refresh_failures.fetch_add(1, Ordering::Relaxed);
+        *self
+            .last_failure
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(SystemTime::now());
+    }
+
+    /// Records a request answered with a stale token.
+    pub(crate) fn stale_hit(&self) {
+        self.stale_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the current values of the counters.
+    pub(crate) fn snapshot(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            refreshes: self.refreshes.load(Ordering::Relaxed),
+            refresh_failures: self.refresh_failures.load(Ordering::Relaxed),
+            stale_hits: self.stale_hits.load(Ordering::Relaxed),
+            last_refresh: *self
+                .last_refresh
+                .read()
+                .unwrap_or_else(PoisonError::into_inner),
+            last_failure: *self
+                .last_failure
+                .read()
+                .unwrap_or_else(PoisonError::into_inner),
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! A signer that reuses its token and replaces it ahead of expiry.
+
+use std::fmt;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, SystemTime};
+
+use crate::cache_stats::{CacheStats, StatsRecorder};
+use crate::database_credentials::{DatabaseCredentials, Password, PasswordFuture};
+use crate::refresh_schedule::RefreshSchedule;
+use crate::sign::{AuthToken, Signer};
+
+/// A callback invoked with the error of every failed token refresh.
+type RefreshFailureFn = dyn Fn(&crate::Error) + Send + Sync;
+
+/// A shareable refresh failure callback.
+#[derive(Clone)]
+struct RefreshFailureHook(Arc<RefreshFailureFn>);
+
+impl fmt::Debug for RefreshFailureHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RefreshFailureHook")
+    }
+}
+
+/// A signer that returns the same token until it nears expiry.
+///
+/// Once the cached token has less than the refresh margin of validity left,
+/// the next request mints its replacement, so callers never receive a token
+/// that expires seconds later, e.g. while a connection is being established.
+///
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// # use aws_rds_signer::{CachingSigner, Signer};
+/// # async fn connect(signer: Signer) -> Result<(), aws_rds_signer::Error> {
+/// let signer = CachingSigner::new(signer)
+///     .refresh_margin(Duration::from_secs(120))
+///     .refresh_jitter(Duration::from_secs(30));
+/// // On every connect:
+/// let token = signer.fetch_token().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CachingSigner {
+    /// The signer minting tokens.
+    signer: Signer,
+    /// When the cached token is replaced.
+    schedule: RefreshSchedule,
+    /// The cached token and when it is due for replacement.
+    token: RwLock<Option<(AuthToken, SystemTime)>>,
+    /// Whether the cached token is returned while unexpired if minting its replacement fails.
+    serve_stale: bool,
+    /// The callback invoked when minting a replacement fails.
+    on_refresh_failure: Option<RefreshFailureHook>,
+    /// Counters of cache hits, misses, and refreshes.
+    stats: StatsRecorder,
+    /// Held while a token is minted, so concurrent requests wait for it instead of minting their own.
+    refreshing: tokio::sync::Mutex<()>,
+}
+
+impl CachingSigner {
+    /// Creates a caching signer that replaces its token two minutes before it expires.
+    ///
+    /// # Arguments
+    /// * `signer` - The signer minting tokens
+    #[must_use]
+    pub fn new(signer: Signer) -> Self {
+        Self {
+            signer,
+            schedule: RefreshSchedule::new(),
+            token: RwLock::default(),
+            serve_stale: false,
+            on_refresh_failure: None,
+            stats: StatsRecorder::default(),
+            refreshing: tokio::sync::Mutex::const_new(()),
+        }
+    }
+
+    /// Sets how long before expiry the cached token is replaced.
+    ///
+    /// A margin of at least the token lifetime disables caching, since every
+    /// new token is already due for replacement.
+    ///
+    /// # Arguments
+    /// * `refresh_margin` - The minimum remaining lifetime of a returned token
+    #[must_use]
+    pub const fn refresh_margin(mut self, refresh_margin: Duration) -> Self {
+        self.schedule = self.schedule.margin(refresh_margin);
+        self
+    }
+
+    /// Sets the longest random delay by which the token is replaced earlier than the refresh margin.
+    ///
+    /// The delay is drawn once per token. Defaults to zero; set it when many
+    /// instances start together, so their refreshes do not all fetch
+    /// credentials from IMDS or STS at the same instant.
+    ///
+    /// # Arguments
+    /// * `refresh_jitter` - The upper bound of the random delay
+    #[must_use]
+    pub const fn refresh_jitter(mut self, refresh_jitter: Duration) -> Self {
+        self.schedule = self.schedule.jitter(refresh_jitter);
+        self
+    }
+
+    /// Sets whether the cached token is returned if minting its replacement fails.
+    ///
+    /// When enabled, a failed refresh, e.g. during a brief STS or IMDS outage,
+    /// returns the previous token for as long as it has not expired, and the
+    /// next request tries again. Once it expires, the error is returned.
+    /// Disabled by default.
+    ///
+    /// # Arguments
+    /// * `serve_stale` - Whether to fall back to the unexpired cached token
+    #[must_use]
+    pub const fn serve_stale(mut self, serve_stale: bool) -> Self {
+        self.serve_stale = serve_stale;
+        self
+    }
+
+    /// Sets a callback invoked with the error of every failed refresh.
+    ///
+    /// It is called whether or not a stale token is returned instead, so
+    /// failures hidden by [`Self::serve_stale`] can be counted or alerted on.
+    ///
+    /// # Arguments
+    /// * `on_refresh_failure` - The callback receiving the refresh error
+    #[must_use]
+    pub fn on_refresh_failure(
+        mut self,
+        on_refresh_failure: impl Fn(&crate::Error) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_refresh_failure = Some(RefreshFailureHook(Arc::new(on_refresh_failure)));
+        self
+    }
+
+    /// Returns the signer minting tokens.
+    #[must_use]
+    pub const fn signer(&self) -> &Signer {
+        &self.signer
+    }
+
+    /// Returns the cached token, or mints a new one if it is within the refresh margin of expiry.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Signer::fetch_token`], unless
+    /// [`Self::serve_stale`] is enabled and the cached token has not expired.
+    pub async fn fetch_token(&self) -> Result<AuthToken, crate::Error> {
+        self.fetch_token_if(|_, refresh_at| refresh_at > SystemTime::now())
+            .await
+    }
+
+    /// Returns the cached token if it is valid for at least `min_ttl`, or mints a new one first.
+    ///
+    /// Unlike [`Self::fetch_token`], the refresh margin is ignored, so each
+    /// caller states how long it needs the token, e.g. a connection pool
+    /// passing its connect timeout. A `min_ttl` above the token lifetime
+    /// mints a new token on every call.
+    ///
+    /// # Arguments
+    /// * `min_ttl` - The minimum remaining lifetime of the returned token
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Signer::fetch_token`], unless
+    /// [`Self::serve_stale`] is enabled and the cached token has not expired;
+    /// that token may then be valid for less than `min_ttl`.
+    pub async fn get_token(&self, min_ttl: Duration) -> Result<AuthToken, crate::Error> {
+        self.fetch_token_if(|token, _| token.ttl_remaining() >= min_ttl)
+            .await
+    }
+
+    /// Mints and caches a token ahead of its first use, e.g. during startup.
+    ///
+    /// The first connection then gets the cached token instead of waiting for
+    /// credential resolution and signing. A cached token that is not yet due
+    /// for replacement is kept.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::fetch_token`].
+    pub async fn warm(&self) -> Result<(), crate::Error> {
+        self.fetch_token().await.map(drop)
+    }
+
+    /// Discards the cached token, so the next request mints a new one.
+    ///
+    /// Call it after the database rejects a token, e.g. following an IAM
+    /// policy change, instead of waiting for the token to be replaced.
+    pub fn invalidate(&self) {
+        *self.token.write().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    /// Returns the cache's hit, miss, and refresh counters.
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        self.stats.snapshot()
+    }
+
+    /// Returns the cached token if `usable` accepts it, or mints a new one.
+    ///
+    /// Concurrent requests wait for a single refresh and then check the cached token again.
+    async fn fetch_token_if(
+        &self,
+        usable: impl Fn(&AuthToken, SystemTime) -> bool,
+    ) -> Result<AuthToken, crate::Error> {
+        if let Some(token) = self.cached_token(&usable) {
+            self.stats.hit();
+            return Ok(token);
+        }
+        let _refreshing = self.refreshing.lock().await;
+        if let Some(token) = self.cached_token(&usable) {
+            self.stats.hit();
+            return Ok(token);
+        }
+        self.refresh().await
+    }
+
+    /// Mints and caches a new token, falling back to the unexpired cached token if allowed.
+    async fn refresh(&self) -> Result<AuthToken, crate::Error> {
+        self.stats.miss();
+        let token = match Box::pin(self.signer.fetch_token()).await {
+            Ok(token) => token,
+            Err(e) => return self.refresh_failed(e),
+        };
+        self.stats.refreshed();
+        let refresh_at = self.schedule.refresh_at(&token, Duration::MAX);
+        *self.token.write().unwrap_or_else(PoisonError::into_inner) =
+            Some((token.clone(), refresh_at));
+        Ok(token)
+    }
+
+    /// Reports a failed refresh and returns the cached token if stale tokens are served and it has not expired.
+    fn refresh_failed(&self, error: crate::Error) -> Result<AuthToken, crate::Error> {
+        self.stats.refresh_failed();
+        if let Some(hook) = &self.on_refresh_failure {
+            (hook.0)(&error);
+        }
+        if !self.serve_stale {
+            return Err(error);
+        }
+        match self.cached_token(|token, _| !token.is_expired()) {
+            Some(token) => {
+                tracing::warn!(
+                    error = %error,
+                    expires_at = %token.expires_at_rfc3339(),
+                    "failed to refresh the RDS token; using the cached token"
+                );
+                self.stats.stale_hit();
+                Ok(token)
+            }
+            None => Err(error),
+        }
+    }
+
+    /// Returns the cached token if `usable` accepts it and the time it is due for replacement.
+    fn cached_token(
+        &self,
+        usable: impl FnOnce(&AuthToken, SystemTime) -> bool,
+    ) -> Option<AuthToken> {
+        self.token
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .filter(|(token, refresh_at)| usable(token, *refresh_at))
+            .map(|(token, _)| token.clone())
+    }
+}
+
+impl DatabaseCredentials for CachingSigner {
+    /// Returns the cached token as the password, minting a new one when it nears expiry.
+    fn password(&self) -> PasswordFuture<'_> {
+        Box::pin(async { self.fetch_token().await.map(Password::from) })
+    }
+}
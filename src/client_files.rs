@@ -0,0 +1,200 @@
+//! Client configuration files holding authentication tokens.
+//!
+//! Command-line clients such as `psql`, `pg_dump`, `mysql`, and `mysqldump`
+//! read passwords from files; the writers in this module store tokens in
+//! those files, readable only by their owner. Services managed by systemd
+//! can load them as credentials instead; see
+//! [`AuthToken::write_systemd_credentials`].
+
+use std::fs::{DirBuilder, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::sign::AuthToken;
+
+impl AuthToken {
+    /// Inserts or updates the token in a `.pgpass` password file.
+    ///
+    /// The `host:port:dbname:user:token` line replaces any line for the same
+    /// host, port, database, and user, and other lines are kept. The file is
+    /// replaced atomically, with `0600` permissions, since libpq ignores
+    /// password files readable by others.
+    ///
+    /// # Arguments
+    /// * `path` - The password file (e.g., `~/.pgpass` or `$PGPASSFILE`)
+    /// * `dbname` - The database the line applies to, or `*` for any database
+    ///
+    /// # Errors
+    /// * `IoError` - If the file cannot be read or written
+    pub fn write_pgpass(&self, path: impl AsRef<Path>, dbname: &str) -> Result<(), crate::Error> {
+        let path = path.as_ref();
+        let existing = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(io_error(path, &e)),
+        };
+        let prefix = format!(
+            "{}:{}:{}:{}:",
+            pgpass_field(self.host()),
+            self.port(),
+            pgpass_field(dbname),
+            pgpass_field(self.user())
+        );
+        let entry = format!("{prefix}{}", pgpass_field(self.value()));
+
+        let mut contents = String::with_capacity(existing.len() + entry.len() + 1);
+        let mut written = false;
+        for line in existing.lines() {
+            if line.starts_with(&prefix) {
+                if written {
+                    continue;
+                }
+                written = true;
+                contents.push_str(&entry);
+            } else {
+                contents.push_str(line);
+            }
+            contents.push('\n');
+        }
+        if !written {
+            contents.push_str(&entry);
+            contents.push('\n');
+        }
+        write_private(path, &contents)
+    }
+
+    /// Writes the token to a file, for sidecars and other processes that read it from disk.
+    ///
+    /// The file holds only the token, without a trailing newline. It is
+    /// written to a temporary file with `0600` permissions that is then
+    /// renamed over `path`, so readers never see a partially written token.
+    ///
+    /// # Arguments
+    /// * `path` - The token file (e.g., `/run/secrets/rds-token`)
+    ///
+    /// # Errors
+    /// * `IoError` - If the file cannot be written or renamed
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> Result<(), crate::Error> {
+        write_private(path.as_ref(), self.value())
+    }
+
+    /// Returns a `MySQL` option file `[client]` section for the token.
+    ///
+    /// The section sets the host, port, user, and token as the password, and
+    /// requires TLS and the cleartext authentication plugin, which RDS needs
+    /// for IAM authentication.
+    #[must_use]
+    pub fn my_cnf(&self) -> String {
+        format!(
+            "[client]\nhost={}\nport={}\nuser={}\npassword={}\nssl-mode=REQUIRED\nenable-cleartext-plugin\n",
+            my_cnf_value(self.host()),
+            self.port(),
+            my_cnf_value(self.user()),
+            my_cnf_value(self.value())
+        )
+    }
+
+    /// Writes the [`Self::my_cnf`] section to a `MySQL` option file.
+    ///
+    /// The file is replaced atomically, with `0600` permissions. Pass it to
+    /// clients with `--defaults-extra-file`, e.g.,
+    /// `mysqldump --defaults-extra-file=/run/app/rds.cnf app`.
+    ///
+    /// # Arguments
+    /// * `path` - The option file
+    ///
+    /// # Errors
+    /// * `IoError` - If the file cannot be written
+    pub fn write_my_cnf(&self, path: impl AsRef<Path>) -> Result<(), crate::Error> {
+        write_private(path.as_ref(), &self.my_cnf())
+    }
+
+    /// Writes the token to a directory for systemd's `LoadCredential=`.
+    ///
+    /// The directory gets one file per field, `host`, `port`, `user`, and
+    /// `password`, each without a trailing newline and replaced atomically
+    /// with `0600` permissions; it is created with `0700` permissions if
+    /// needed. Loading the directory under an ID, e.g.,
+    /// `LoadCredential=db:/run/rds-token`, exposes the fields to the service
+    /// as `$CREDENTIALS_DIRECTORY/db_password` and so on. systemd copies
+    /// credentials when the service starts, so restart it after each refresh,
+    /// e.g., from the timer that runs the refresh.
+    ///
+    /// # Arguments
+    /// * `dir` - The credential directory (e.g., `/run/rds-token`)
+    ///
+    /// # Errors
+    /// * `IoError` - If the directory cannot be created or a file cannot be written
+    pub fn write_systemd_credentials(&self, dir: impl AsRef<Path>) -> Result<(), crate::Error> {
+        let dir = dir.as_ref();
+        let mut builder = DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        builder.create(dir).map_err(|e| io_error(dir, &e))?;
+        for (name, value) in [
+            ("host", self.host()),
+            ("port", &self.port().to_string()),
+            ("user", self.user()),
+            ("password", self.value()),
+        ] {
+            write_private(&dir.join(name), value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Escapes `:` and `\` in a `.pgpass` field.
+fn pgpass_field(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(':', "\\:")
+}
+
+/// Double-quotes a `MySQL` option file value, escaping `\` and `"`.
+fn my_cnf_value(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Replaces the file at `path` with `contents`, readable only by its owner.
+///
+/// The contents are written to a temporary file in the same directory, which
+/// is then renamed over `path`, so readers see either the old or the new
+/// contents and never a partial write.
+fn write_private(path: &Path, contents: &str) -> Result<(), crate::Error> {
+    static TEMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+    let mut temp_name = path
+        .file_name()
+        .ok_or_else(|| crate::Error::IoError(format!("{}: not a file path", path.display())))?
+        .to_os_string();
+    temp_name.push(format!(
+        ".{}-{}.tmp",
+        std::process::id(),
+        TEMP_FILES.fetch_add(1, Ordering::Relaxed)
+    ));
+    let temp_path = path.with_file_name(temp_name);
+    let result = write_new(&temp_path, contents)
+        .and_then(|()| std::fs::rename(&temp_path, path))
+        .map_err(|e| io_error(path, &e));
+    if result.is_err() {
+        // The temporary file may not exist; the original error is more useful.
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Creates the file at `path` with owner-only permissions and writes `contents` to disk.
+fn write_new(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()
+}
+
+/// Returns an `IoError` for a failed operation on `path`.
+fn io_error(path: &Path, e: &std::io::Error) -> crate::Error {
+    crate::Error::IoError(format!("{}: {e}", path.display()))
+}
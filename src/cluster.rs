@@ -0,0 +1,76 @@
+//! Token generation for the writer and reader endpoints of a cluster.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::sign::{AuthToken, Signer};
+
+/// A signer for the writer and reader endpoints of an Aurora or Multi-AZ cluster.
+///
+/// Every endpoint shares the port, user, region, and credentials of the writer
+/// signer, including its credentials cache, so credentials are resolved once
+/// for the whole cluster. A connect host set on the writer applies to the
+/// writer only; readers are dialed at their own hostnames.
+#[derive(Debug)]
+pub struct ClusterSigner {
+    /// The signer for the writer endpoint.
+    writer: Signer,
+    /// The signers for the reader endpoints.
+    readers: Vec<Signer>,
+    /// The index of the reader endpoint used for the next reader token.
+    next_reader: AtomicUsize,
+}
+
+impl ClusterSigner {
+    /// Creates a cluster signer from the writer signer and the reader endpoint hostnames.
+    ///
+    /// # Arguments
+    /// * `writer` - The signer for the writer (cluster) endpoint
+    /// * `reader_hosts` - The reader endpoints (e.g., `mycluster.cluster-ro-xxxx.us-east-1.rds.amazonaws.com`)
+    #[must_use]
+    pub fn new(writer: Signer, reader_hosts: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let readers = reader_hosts
+            .into_iter()
+            .map(|host| writer.with_endpoint(host, writer.port()))
+            .collect();
+        Self {
+            writer,
+            readers,
+            next_reader: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the signer for the writer endpoint.
+    #[must_use]
+    pub const fn writer(&self) -> &Signer {
+        &self.writer
+    }
+
+    /// Returns the signers for the reader endpoints.
+    #[must_use]
+    pub fn readers(&self) -> &[Signer] {
+        &self.readers
+    }
+
+    /// Generates a token for the writer endpoint.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Signer::fetch_token`].
+    pub async fn fetch_writer_token(&self) -> Result<AuthToken, crate::Error> {
+        self.writer.fetch_token().await
+    }
+
+    /// Generates a token for the next reader endpoint, cycling through them in order.
+    ///
+    /// Without reader endpoints, the token is generated for the writer endpoint.
+    /// The endpoint is the host at the start of the token value.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Signer::fetch_token`].
+    pub async fn fetch_reader_token(&self) -> Result<AuthToken, crate::Error> {
+        if self.readers.is_empty() {
+            return self.fetch_writer_token().await;
+        }
+        let index = self.next_reader.fetch_add(1, Ordering::Relaxed) % self.readers.len();
+        self.readers[index].fetch_token().await
+    }
+}
@@ -0,0 +1,59 @@
+//! Loads signer configuration from TOML files.
+//!
+//! A file defines any number of named databases, so CLI tools and services
+//! can keep endpoint and user definitions out of code:
+//!
+//! ```toml
+//! [databases.analytics]
+//! host = "analytics.xxxx.us-east-1.rds.amazonaws.com"
+//! port = 5432
+//! user = "readonly"
+//! region = "us-east-1"
+//! expires_in = 600
+//! profile = "analytics"
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::sign::SignerBuilder;
+use crate::signer_config::SignerConfig;
+
+/// The contents of a signer configuration file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ConfigFile {
+    /// The databases defined in the file, by name.
+    #[serde(default)]
+    databases: HashMap<String, SignerConfig>,
+}
+
+impl ConfigFile {
+    /// Reads and parses the configuration file at `path`.
+    pub(crate) fn load(path: &Path) -> Result<Self, crate::Error> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            crate::Error::ParseError(format!("failed to read {}: {e}", path.display()))
+        })?;
+        Self::parse(&contents)
+            .map_err(|e| crate::Error::ParseError(format!("{}: {e}", path.display())))
+    }
+
+    /// Parses the contents of a configuration file.
+    pub(crate) fn parse(contents: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(contents)
+    }
+
+    /// Applies the settings of the database called `name` to `builder`.
+    pub(crate) fn configure(
+        &self,
+        name: &str,
+        builder: SignerBuilder,
+    ) -> Result<SignerBuilder, crate::Error> {
+        let database = self.databases.get(name).ok_or_else(|| {
+            crate::Error::ParseError(format!("no database named `{name}` is configured"))
+        })?;
+        Ok(database.configure(builder))
+    }
+}
@@ -0,0 +1,53 @@
+//! Integration with the `config` crate (config-rs).
+//!
+//! Services managing many RDS endpoints can declare them as named targets:
+//!
+//! ```toml
+//! [databases.orders]
+//! host = "orders.xxxx.us-east-1.rds.amazonaws.com"
+//! user = "app"
+//!
+//! [databases.analytics]
+//! host = "analytics.xxxx.us-east-1.rds.amazonaws.com"
+//! user = "readonly"
+//! expires_in = 600
+//! ```
+
+use std::collections::HashMap;
+
+use config::Config;
+
+use crate::signer_config::SignerConfig;
+
+impl SignerConfig {
+    /// Deserializes the signer configuration at `key` of a config-rs configuration.
+    ///
+    /// # Arguments
+    /// * `config` - The loaded configuration
+    /// * `key` - The path of the settings (e.g., "database")
+    ///
+    /// # Errors
+    /// * `ParseError` - If the settings are missing or malformed
+    pub fn from_config_rs(config: &Config, key: &str) -> Result<Self, crate::Error> {
+        config
+            .get(key)
+            .map_err(|e| crate::Error::ParseError(format!("{key}: {e}")))
+    }
+
+    /// Deserializes the named signer configurations in the table at `key`.
+    ///
+    /// # Arguments
+    /// * `config` - The loaded configuration
+    /// * `key` - The path of the table of named targets (e.g., "databases")
+    ///
+    /// # Errors
+    /// * `ParseError` - If the table is missing or a target is malformed
+    pub fn named_from_config_rs(
+        config: &Config,
+        key: &str,
+    ) -> Result<HashMap<String, Self>, crate::Error> {
+        config
+            .get(key)
+            .map_err(|e| crate::Error::ParseError(format!("{key}: {e}")))
+    }
+}
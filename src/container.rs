@@ -0,0 +1,230 @@
+//! Loads credentials from a container credentials endpoint.
+//!
+//! ECS task roles and EKS Pod Identity both serve credentials over HTTP from a
+//! local endpoint. The provider in `aws-config` only reads the endpoint and
+//! authorization token from `AWS_CONTAINER_*` environment variables, so this
+//! module implements the same protocol with explicitly configured values.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use aws_config::SdkConfig;
+use aws_credential_types::provider::error::CredentialsError;
+use aws_credential_types::provider::{self, future, ProvideCredentials};
+use aws_credential_types::Credentials;
+use aws_smithy_runtime::client::orchestrator::operation::Operation;
+use aws_smithy_runtime::client::retries::classifiers::{
+    HttpStatusCodeClassifier, TransientErrorClassifier,
+};
+use aws_smithy_runtime_api::client::interceptors::context::Error;
+use aws_smithy_runtime_api::client::orchestrator::{
+    HttpResponse, OrchestratorError, SensitiveOutput,
+};
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_runtime_api::client::runtime_plugin::StaticRuntimePlugin;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::Layer;
+use aws_smithy_types::retry::RetryConfig;
+use aws_smithy_types::timeout::TimeoutConfig;
+use aws_types::os_shim_internal::Env;
+use http::header::{ACCEPT, AUTHORIZATION};
+use http::HeaderValue;
+
+use crate::json_credentials::JsonCredentials;
+
+/// The ECS endpoint that relative credential URIs are resolved against.
+const ECS_ENDPOINT: &str = "http://169.254.170.2";
+
+/// The credentials URI served by the EKS Pod Identity agent.
+const EKS_POD_IDENTITY_URI: &str = "http://169.254.170.23/v1/credentials";
+
+/// The service account token projected into pods that use EKS Pod Identity.
+const EKS_POD_IDENTITY_TOKEN_FILE: &str =
+    "/var/run/secrets/pods.eks.amazonaws.com/serviceaccount/eks-pod-identity-token";
+
+/// The connect timeout for requests to the credentials endpoint.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The read timeout for requests to the credentials endpoint.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Configuration for a container credentials endpoint.
+#[derive(Debug, Clone)]
+pub(crate) struct ContainerEndpoint {
+    /// The full credentials URI, or a path relative to the ECS endpoint.
+    pub(crate) uri: String,
+    /// The file holding the authorization token, re-read on every resolution.
+    pub(crate) auth_token_file: Option<PathBuf>,
+}
+
+impl ContainerEndpoint {
+    /// Returns the endpoint for `uri` with an optional authorization token file.
+    ///
+    /// Without an explicit token file, `AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE`
+    /// is used when set, as custom metadata services and Pod Identity expect.
+    pub(crate) fn new(uri: String, auth_token_file: Option<PathBuf>, env: &Env) -> Self {
+        Self {
+            uri,
+            auth_token_file: auth_token_file.or_else(|| {
+                env.get("AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE")
+                    .ok()
+                    .map(PathBuf::from)
+            }),
+        }
+    }
+
+    /// Returns the endpoint of the EKS Pod Identity agent.
+    ///
+    /// The URI and token file injected by EKS through
+    /// `AWS_CONTAINER_CREDENTIALS_FULL_URI` and `AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE`
+    /// take precedence over the agent's well-known defaults.
+    pub(crate) fn eks_pod_identity(env: &Env) -> Self {
+        Self {
+            uri: env
+                .get("AWS_CONTAINER_CREDENTIALS_FULL_URI")
+                .unwrap_or_else(|_| EKS_POD_IDENTITY_URI.to_string()),
+            auth_token_file: Some(
+                env.get("AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE")
+                    .map_or_else(|_| EKS_POD_IDENTITY_TOKEN_FILE.into(), PathBuf::from),
+            ),
+        }
+    }
+
+    /// Builds a credentials provider for this endpoint using the HTTP client of `config`.
+    pub(crate) fn provider(
+        &self,
+        config: &SdkConfig,
+    ) -> Result<ContainerCredentials, crate::Error> {
+        let uri = if self.uri.starts_with('/') {
+            format!("{ECS_ENDPOINT}{}", self.uri)
+        } else {
+            self.uri.clone()
+        };
+        let url = url::Url::parse(&uri).map_err(|e| crate::Error::ParseError(e.to_string()))?;
+        let endpoint = url.origin().ascii_serialization();
+        let mut path = url.path().to_string();
+        if let Some(query) = url.query() {
+            path.push('?');
+            path.push_str(query);
+        }
+
+        let mut builder = Operation::builder()
+            .service_name("ContainerCredentials")
+            .operation_name("LoadCredentials")
+            .with_connection_poisoning()
+            .endpoint_url(&endpoint)
+            .no_auth()
+            .timeout_config(
+                TimeoutConfig::builder()
+                    .connect_timeout(CONNECT_TIMEOUT)
+                    .read_timeout(READ_TIMEOUT)
+                    .build(),
+            )
+            .runtime_plugin(StaticRuntimePlugin::new().with_config({
+                let mut layer = Layer::new("SensitiveOutput");
+                layer.store_put(SensitiveOutput);
+                layer.freeze()
+            }));
+        if let Some(http_client) = config.http_client() {
+            builder = builder.http_client(http_client);
+        }
+        if let Some(time_source) = config.time_source() {
+            builder = builder.time_source(time_source);
+        }
+        if let Some(sleep_impl) = config.sleep_impl() {
+            builder = builder
+                .standard_retry(&RetryConfig::standard())
+                .retry_classifier(TransientErrorClassifier::<Error>::new())
+                .retry_classifier(HttpStatusCodeClassifier::default())
+                .sleep_impl(sleep_impl);
+        } else {
+            builder = builder.no_retry();
+        }
+        let operation = builder
+            .serializer(move |input: Authorization| {
+                let mut request = http::Request::builder()
+                    .uri(path.clone())
+                    .header(ACCEPT, "application/json");
+                if let Some(auth) = input.0 {
+                    request = request.header(AUTHORIZATION, auth);
+                }
+                Ok(request.body(SdkBody::empty())?.try_into()?)
+            })
+            .deserializer(parse_response)
+            .build();
+
+        Ok(ContainerCredentials {
+            operation,
+            auth_token_file: self.auth_token_file.clone(),
+        })
+    }
+}
+
+/// The authorization header sent to the credentials endpoint.
+struct Authorization(Option<HeaderValue>);
+
+impl fmt::Debug for Authorization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Authorization(** redacted **)")
+    }
+}
+
+/// A credentials provider for a container credentials endpoint.
+#[derive(Debug)]
+pub(crate) struct ContainerCredentials {
+    /// The HTTP operation that fetches credentials.
+    operation: Operation<Authorization, Credentials, CredentialsError>,
+    /// The file holding the authorization token.
+    auth_token_file: Option<PathBuf>,
+}
+
+impl ContainerCredentials {
+    /// Reads the authorization token and requests credentials from the endpoint.
+    async fn credentials(&self) -> provider::Result {
+        let auth = match &self.auth_token_file {
+            Some(path) => {
+                let token = tokio::fs::read(path)
+                    .await
+                    .map_err(CredentialsError::provider_error)?;
+                Some(
+                    HeaderValue::from_bytes(token.trim_ascii())
+                        .map_err(CredentialsError::invalid_configuration)?,
+                )
+            }
+            None => None,
+        };
+        match self.operation.invoke(Authorization(auth)).await {
+            Ok(credentials) => Ok(credentials),
+            Err(SdkError::ServiceError(context)) => Err(context.into_err()),
+            Err(other) => Err(CredentialsError::provider_error(other)),
+        }
+    }
+}
+
+impl ProvideCredentials for ContainerCredentials {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(self.credentials())
+    }
+}
+
+/// Parses the credentials returned by the endpoint.
+fn parse_response(
+    response: &HttpResponse,
+) -> Result<Credentials, OrchestratorError<CredentialsError>> {
+    if !response.status().is_success() {
+        return Err(OrchestratorError::operation(
+            CredentialsError::provider_error(format!(
+                "container credentials endpoint returned status {}",
+                response.status().as_u16()
+            )),
+        ));
+    }
+    let body = response.body().bytes().unwrap_or_default();
+    JsonCredentials::parse(body)
+        .and_then(|credentials| credentials.into_credentials("ContainerCredentials"))
+        .map_err(OrchestratorError::operation)
+}
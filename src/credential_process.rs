@@ -0,0 +1,90 @@
+//! Runs `credential_process` helpers to obtain AWS credentials.
+//!
+//! Unlike the provider in `aws-config`, the helper is spawned with a bounded
+//! lifetime: when the configured timeout elapses the process is killed so a
+//! hung helper cannot stall token generation indefinitely.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use aws_credential_types::provider::error::CredentialsError;
+use aws_credential_types::provider::{self, future, ProvideCredentials};
+use aws_smithy_types::Number;
+use tokio::process::Command;
+
+use crate::json_credentials::JsonCredentials;
+
+/// A credentials provider that runs an external command printing credentials as JSON.
+#[derive(Debug, Clone)]
+pub(crate) struct CredentialProcess {
+    /// The command line, executed through the platform shell.
+    command: String,
+    /// The maximum time the process may run before it is killed.
+    timeout: Option<Duration>,
+}
+
+impl CredentialProcess {
+    /// Creates a provider for `command`, killed after `timeout` if one is given.
+    pub(crate) fn new(command: impl Into<String>, timeout: Option<Duration>) -> Self {
+        Self {
+            command: command.into(),
+            timeout,
+        }
+    }
+
+    /// Runs the process and parses the credentials it prints.
+    async fn credentials(&self) -> provider::Result {
+        let mut command = if cfg!(windows) {
+            let mut command = Command::new("cmd.exe");
+            command.args(["/C", &self.command]);
+            command
+        } else {
+            let mut command = Command::new("sh");
+            command.args(["-c", &self.command]);
+            command
+        };
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let output = command.output();
+        let output = match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, output)
+                .await
+                .map_err(|_| CredentialsError::provider_timed_out(timeout))?,
+            None => output.await,
+        }
+        .map_err(CredentialsError::provider_error)?;
+
+        if !output.status.success() {
+            return Err(CredentialsError::provider_error(format!(
+                "credential process exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        parse_credentials(&output.stdout)
+    }
+}
+
+impl ProvideCredentials for CredentialProcess {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(self.credentials())
+    }
+}
+
+/// Parses the version 1 JSON document printed by a credential process.
+fn parse_credentials(output: &[u8]) -> provider::Result {
+    let credentials = JsonCredentials::parse(output)?;
+    if credentials.version != Some(Number::PosInt(1)) {
+        return Err(CredentialsError::invalid_configuration(
+            "credential process output must have `Version` 1",
+        ));
+    }
+    credentials.into_credentials("CredentialProcess")
+}
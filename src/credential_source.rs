@@ -0,0 +1,139 @@
+//! A minimal trait for plugging custom credential stores into the signer.
+//!
+//! Implementing `ProvideCredentials` ties callers to the `aws-credential-types`
+//! error and future types. [`CredentialSource`] only asks for an async method
+//! returning plain [`SourceCredentials`], which suits OS keyrings, `aws-vault`
+//! exports, or HSM-derived keys.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use aws_credential_types::provider::error::CredentialsError;
+use aws_credential_types::provider::{self, future, ProvideCredentials};
+use aws_credential_types::Credentials;
+
+/// The error type returned by a [`CredentialSource`].
+pub type CredentialSourceError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The future returned by [`CredentialSource::credentials`].
+pub type CredentialSourceFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<SourceCredentials, CredentialSourceError>> + Send + 'a>>;
+
+/// A custom store of AWS credentials used to sign tokens.
+///
+/// # Example
+///
+/// ```rust
+/// use aws_rds_signer::{CredentialSource, CredentialSourceFuture, SourceCredentials};
+///
+/// #[derive(Debug)]
+/// struct Keyring;
+///
+/// impl CredentialSource for Keyring {
+///     fn credentials(&self) -> CredentialSourceFuture<'_> {
+///         Box::pin(async { Ok(SourceCredentials::new("AKIDEXAMPLE", "secret")) })
+///     }
+/// }
+/// ```
+pub trait CredentialSource: Send + Sync + fmt::Debug {
+    /// Loads the current credentials.
+    ///
+    /// Called whenever the signer resolves credentials, i.e. on the first token
+    /// request and again after the returned credentials expire.
+    fn credentials(&self) -> CredentialSourceFuture<'_>;
+}
+
+/// AWS credentials returned by a [`CredentialSource`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct SourceCredentials {
+    /// The access key ID.
+    access_key_id: String,
+    /// The secret access key.
+    secret_access_key: String,
+    /// The session token of temporary credentials.
+    session_token: Option<String>,
+    /// The time at which temporary credentials expire.
+    expiration: Option<SystemTime>,
+}
+
+impl fmt::Debug for SourceCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SourceCredentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"** redacted **")
+            .field(
+                "session_token",
+                &self.session_token.as_ref().map(|_| "** redacted **"),
+            )
+            .field("expiration", &self.expiration)
+            .finish()
+    }
+}
+
+impl SourceCredentials {
+    /// Creates long-term credentials from an access key pair.
+    #[must_use]
+    pub fn new(access_key_id: impl Into<String>, secret_access_key: impl Into<String>) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+            expiration: None,
+        }
+    }
+
+    /// Sets the session token of temporary credentials.
+    #[must_use]
+    pub fn session_token(mut self, session_token: impl Into<String>) -> Self {
+        self.session_token = Some(session_token.into());
+        self
+    }
+
+    /// Sets the time at which the credentials expire.
+    ///
+    /// The signer asks the source for new credentials once this time passes.
+    #[must_use]
+    pub const fn expiration(mut self, expiration: SystemTime) -> Self {
+        self.expiration = Some(expiration);
+        self
+    }
+}
+
+impl From<SourceCredentials> for Credentials {
+    fn from(credentials: SourceCredentials) -> Self {
+        Self::new(
+            credentials.access_key_id,
+            credentials.secret_access_key,
+            credentials.session_token,
+            credentials.expiration,
+            "CredentialSource",
+        )
+    }
+}
+
+/// Adapts a [`CredentialSource`] to a credentials provider.
+#[derive(Debug, Clone)]
+pub(crate) struct CredentialSourceProvider(pub(crate) Arc<dyn CredentialSource>);
+
+impl CredentialSourceProvider {
+    /// Loads credentials from the source.
+    async fn credentials(&self) -> provider::Result {
+        self.0
+            .credentials()
+            .await
+            .map(Credentials::from)
+            .map_err(CredentialsError::provider_error)
+    }
+}
+
+impl ProvideCredentials for CredentialSourceProvider {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(self.credentials())
+    }
+}
@@ -0,0 +1,733 @@
+//! Credential configuration for the RDS signer.
+//!
+//! This module provides the types used to describe how the signer obtains the
+//! AWS credentials that sign authentication tokens, beyond the default chain.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use aws_config::ecs::EcsCredentialsProvider;
+use aws_config::environment::{
+    EnvironmentVariableCredentialsProvider, EnvironmentVariableRegionProvider,
+};
+use aws_config::meta::credentials::CredentialsProviderChain;
+use aws_config::meta::region::RegionProviderChain;
+use aws_config::profile::{
+    ProfileFileCredentialsProvider, ProfileFileRegionProvider, ProfileFileTokenProvider,
+};
+use aws_config::provider_config::ProviderConfig;
+use aws_config::sts::AssumeRoleProvider;
+use aws_config::web_identity_token::WebIdentityTokenCredentialsProvider;
+use aws_config::{ConfigLoader, SdkConfig};
+use aws_credential_types::provider::error::CredentialsError;
+use aws_credential_types::provider::{self, future, ProvideCredentials, SharedCredentialsProvider};
+use aws_credential_types::Credentials;
+use aws_runtime::env_config::file::EnvConfigFiles;
+use aws_sdk_sts::types::PolicyDescriptorType;
+use aws_smithy_runtime_api::client::http::SharedHttpClient;
+use aws_smithy_runtime_api::client::identity::Identity;
+use aws_types::os_shim_internal::{Env, Fs};
+use aws_types::region::Region;
+
+use crate::credential_process::CredentialProcess;
+
+/// An async callback that returns the current MFA token code.
+type TokenCodeFn = dyn Fn() -> Pin<Box<dyn Future<Output = String> + Send>> + Send + Sync;
+
+/// A shareable MFA token code callback.
+#[derive(Clone)]
+struct TokenCodeProvider(Arc<TokenCodeFn>);
+
+impl fmt::Debug for TokenCodeProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TokenCodeProvider")
+    }
+}
+
+/// Callbacks are equal only if they are the same shared callback.
+impl PartialEq for TokenCodeProvider {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for TokenCodeProvider {}
+
+impl Hash for TokenCodeProvider {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.0).cast::<()>().hash(state);
+    }
+}
+
+/// The global STS endpoint, which only accepts requests signed for `us-east-1`.
+const STS_GLOBAL_ENDPOINT: &str = "https://sts.amazonaws.com";
+
+/// The STS endpoint used to assume roles and exchange web identity tokens.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum StsEndpoint {
+    /// The regional endpoint of the signing region (e.g., `sts.eu-west-1.amazonaws.com`).
+    #[default]
+    Regional,
+    /// The legacy global endpoint, `sts.amazonaws.com`.
+    Global,
+    /// A custom endpoint URL, such as an interface VPC endpoint.
+    Url(String),
+}
+
+/// The endpoint variants used for STS, SSO, and container credential requests.
+///
+/// RDS instance endpoints have no FIPS or dual-stack hostnames of their own:
+/// the same endpoint serves IPv4 and IPv6 clients, so the variants apply only
+/// to the AWS APIs called while resolving credentials.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) struct EndpointVariants {
+    /// Whether FIPS endpoints are used.
+    pub(crate) fips: bool,
+    /// Whether dual-stack (IPv4 and IPv6) endpoints are used.
+    pub(crate) dual_stack: bool,
+}
+
+impl EndpointVariants {
+    /// Turns on the enabled variants in an AWS configuration `builder`.
+    ///
+    /// Disabled variants keep the setting of the configuration, which may come
+    /// from `AWS_USE_FIPS_ENDPOINT` or `AWS_USE_DUALSTACK_ENDPOINT`.
+    pub(crate) fn apply(self, builder: &mut aws_types::sdk_config::Builder) {
+        if self.fips {
+            builder.set_use_fips(Some(true));
+        }
+        if self.dual_stack {
+            builder.set_use_dual_stack(Some(true));
+        }
+    }
+
+    /// Returns whether any variant is enabled.
+    pub(crate) const fn any(self) -> bool {
+        self.fips || self.dual_stack
+    }
+}
+
+/// Builds the configuration for STS clients from the signer's AWS configuration.
+///
+/// The signing region is used when the configuration has none, and the
+/// endpoint override is applied to STS calls only.
+pub(crate) fn sts_config(
+    config: &SdkConfig,
+    region: &str,
+    endpoint: Option<&StsEndpoint>,
+) -> SdkConfig {
+    let mut builder = config.to_builder();
+    if config.region().is_none() {
+        builder.set_region(Some(Region::new(region.to_string())));
+    }
+    match endpoint {
+        Some(StsEndpoint::Global) => {
+            builder.set_region(Some(Region::from_static("us-east-1")));
+            builder.set_endpoint_url(Some(STS_GLOBAL_ENDPOINT.to_string()));
+        }
+        Some(StsEndpoint::Url(url)) => {
+            builder.set_endpoint_url(Some(url.clone()));
+        }
+        Some(StsEndpoint::Regional) | None => {}
+    }
+    builder.build()
+}
+
+/// Converts the credentials returned by STS into AWS credentials.
+fn sts_credentials(
+    credentials: Option<aws_sdk_sts::types::Credentials>,
+    provider_name: &'static str,
+) -> provider::Result {
+    let credentials =
+        credentials.ok_or_else(|| CredentialsError::unhandled("STS did not return credentials"))?;
+    Ok(Credentials::new(
+        credentials.access_key_id,
+        credentials.secret_access_key,
+        Some(credentials.session_token),
+        SystemTime::try_from(credentials.expiration).ok(),
+        provider_name,
+    ))
+}
+
+/// Configuration for assuming an IAM role before signing tokens.
+///
+/// The role is assumed through STS using the base credentials of the signer,
+/// which makes cross-account database connections possible without wiring up
+/// `aws-sdk-sts` by hand.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssumeRole {
+    /// The ARN of the role to assume.
+    role_arn: String,
+    /// The external ID required by the role's trust policy, if any.
+    external_id: Option<String>,
+    /// The name of the assumed role session.
+    session_name: Option<String>,
+    /// An inline IAM policy (JSON) that further restricts the session.
+    session_policy: Option<String>,
+    /// ARNs of managed policies that further restrict the session.
+    policy_arns: Vec<String>,
+    /// The serial number or ARN of the MFA device required by the role.
+    serial_number: Option<String>,
+    /// The callback invoked to obtain the MFA token code.
+    token_code: Option<TokenCodeProvider>,
+}
+
+impl AssumeRole {
+    /// Creates a new assume-role configuration for the given role.
+    ///
+    /// # Arguments
+    /// * `role_arn` - The role ARN (e.g., `arn:aws:iam::123456789012:role/db-access`)
+    #[must_use]
+    pub fn new(role_arn: impl Into<String>) -> Self {
+        Self {
+            role_arn: role_arn.into(),
+            external_id: None,
+            session_name: None,
+            session_policy: None,
+            policy_arns: Vec::new(),
+            serial_number: None,
+            token_code: None,
+        }
+    }
+
+    /// Returns the ARN of the role to assume.
+    pub(crate) fn role_arn(&self) -> &str {
+        &self.role_arn
+    }
+
+    /// Sets the external ID required by the role's trust policy.
+    ///
+    /// # Arguments
+    /// * `external_id` - The external ID provided by the role's account owner
+    #[must_use]
+    pub fn external_id(mut self, external_id: impl Into<String>) -> Self {
+        self.external_id = Some(external_id.into());
+        self
+    }
+
+    /// Sets the name of the assumed role session.
+    ///
+    /// # Arguments
+    /// * `session_name` - The session name recorded in `CloudTrail`
+    #[must_use]
+    pub fn session_name(mut self, session_name: impl Into<String>) -> Self {
+        self.session_name = Some(session_name.into());
+        self
+    }
+
+    /// Sets an inline session policy.
+    ///
+    /// The effective permissions of the session are the intersection of the
+    /// role's policies and this policy.
+    ///
+    /// # Arguments
+    /// * `policy` - The IAM policy document as JSON
+    #[must_use]
+    pub fn session_policy(mut self, policy: impl Into<String>) -> Self {
+        self.session_policy = Some(policy.into());
+        self
+    }
+
+    /// Sets managed policies that scope down the session.
+    ///
+    /// # Arguments
+    /// * `policy_arns` - The ARNs of the managed policies
+    #[must_use]
+    pub fn policy_arns<I, S>(mut self, policy_arns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.policy_arns = policy_arns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the MFA device required by the role's trust policy.
+    ///
+    /// A token code callback must also be set with [`Self::token_code_provider`].
+    ///
+    /// # Arguments
+    /// * `serial_number` - The serial number or ARN of the MFA device
+    #[must_use]
+    pub fn serial_number(mut self, serial_number: impl Into<String>) -> Self {
+        self.serial_number = Some(serial_number.into());
+        self
+    }
+
+    /// Sets the callback that obtains an MFA token code.
+    ///
+    /// The callback is invoked every time the role credentials are resolved,
+    /// for example to prompt the user for a TOTP code.
+    ///
+    /// # Arguments
+    /// * `token_code` - An async callback returning the current token code
+    #[must_use]
+    pub fn token_code_provider<F, Fut>(mut self, token_code: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = String> + Send + 'static,
+    {
+        self.token_code = Some(TokenCodeProvider(Arc::new(move || Box::pin(token_code()))));
+        self
+    }
+
+    /// Builds a credentials provider that assumes this role using `base`.
+    ///
+    /// `sts_config` is the configuration prepared by [`sts_config`].
+    pub(crate) async fn provider(
+        &self,
+        sts_config: &SdkConfig,
+        base: SharedCredentialsProvider,
+    ) -> SharedCredentialsProvider {
+        if let Some(serial_number) = &self.serial_number {
+            return SharedCredentialsProvider::new(MfaAssumeRoleProvider::new(
+                self.clone(),
+                serial_number.clone(),
+                sts_config,
+                base,
+            ));
+        }
+        let mut builder = AssumeRoleProvider::builder(&self.role_arn).configure(sts_config);
+        if let Some(external_id) = &self.external_id {
+            builder = builder.external_id(external_id);
+        }
+        if let Some(session_name) = &self.session_name {
+            builder = builder.session_name(session_name);
+        }
+        if let Some(policy) = &self.session_policy {
+            builder = builder.policy(policy);
+        }
+        if !self.policy_arns.is_empty() {
+            builder = builder.policy_arns(self.policy_arns.clone());
+        }
+        SharedCredentialsProvider::new(builder.build_from_provider(base).await)
+    }
+}
+
+/// A credentials provider that assumes a role protected by MFA.
+///
+/// The provider in `aws-config` cannot pass token codes, so the role is
+/// assumed with the STS client directly.
+#[derive(Debug)]
+struct MfaAssumeRoleProvider {
+    /// The STS client authorized by the base credentials.
+    client: aws_sdk_sts::Client,
+    /// The role to assume, including its token code callback.
+    role: AssumeRole,
+    /// The serial number or ARN of the MFA device.
+    serial_number: String,
+}
+
+impl MfaAssumeRoleProvider {
+    /// Creates a provider that calls STS with the `base` credentials.
+    fn new(
+        role: AssumeRole,
+        serial_number: String,
+        sts_config: &SdkConfig,
+        base: SharedCredentialsProvider,
+    ) -> Self {
+        let sts_config = aws_sdk_sts::config::Builder::from(sts_config).credentials_provider(base);
+        Self {
+            client: aws_sdk_sts::Client::from_conf(sts_config.build()),
+            role,
+            serial_number,
+        }
+    }
+
+    /// Prompts for a token code and assumes the role.
+    async fn credentials(&self) -> provider::Result {
+        let token_code = self.role.token_code.as_ref().ok_or_else(|| {
+            CredentialsError::invalid_configuration(
+                "an MFA serial number requires a token code provider",
+            )
+        })?;
+        let token_code = (token_code.0)().await;
+        let policy_arns = self
+            .role
+            .policy_arns
+            .iter()
+            .map(|arn| PolicyDescriptorType::builder().arn(arn).build())
+            .collect::<Vec<_>>();
+        let output = self
+            .client
+            .assume_role()
+            .role_arn(&self.role.role_arn)
+            .role_session_name(
+                self.role
+                    .session_name
+                    .as_deref()
+                    .unwrap_or("aws-rds-signer"),
+            )
+            .set_external_id(self.role.external_id.clone())
+            .set_policy(self.role.session_policy.clone())
+            .set_policy_arns((!policy_arns.is_empty()).then_some(policy_arns))
+            .serial_number(&self.serial_number)
+            .token_code(token_code)
+            .send()
+            .await
+            .map_err(CredentialsError::provider_error)?;
+        sts_credentials(output.credentials, "AssumeRoleWithMfa")
+    }
+}
+
+impl ProvideCredentials for MfaAssumeRoleProvider {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(self.credentials())
+    }
+}
+
+impl From<&str> for AssumeRole {
+    fn from(role_arn: &str) -> Self {
+        Self::new(role_arn)
+    }
+}
+
+impl From<String> for AssumeRole {
+    fn from(role_arn: String) -> Self {
+        Self::new(role_arn)
+    }
+}
+
+/// Configuration for exchanging a web identity token (e.g., EKS IRSA) for credentials.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct WebIdentity {
+    /// The ARN of the role to assume with the web identity token.
+    pub(crate) role_arn: String,
+    /// The file containing the web identity token, re-read on every resolution.
+    pub(crate) token_file: PathBuf,
+}
+
+impl WebIdentity {
+    /// Builds a credentials provider that exchanges the token file for role credentials.
+    ///
+    /// `sts_config` is the configuration prepared by [`sts_config`].
+    pub(crate) fn provider(&self, sts_config: &SdkConfig) -> SharedCredentialsProvider {
+        SharedCredentialsProvider::new(WebIdentityProvider {
+            client: aws_sdk_sts::Client::new(sts_config),
+            web_identity: self.clone(),
+        })
+    }
+}
+
+/// A credentials provider that calls `AssumeRoleWithWebIdentity` with a token file.
+#[derive(Debug)]
+struct WebIdentityProvider {
+    /// The STS client used for the unsigned token exchange.
+    client: aws_sdk_sts::Client,
+    /// The role and token file to exchange.
+    web_identity: WebIdentity,
+}
+
+impl WebIdentityProvider {
+    /// Reads the current token and exchanges it for role credentials.
+    async fn credentials(&self) -> provider::Result {
+        let token = tokio::fs::read_to_string(&self.web_identity.token_file)
+            .await
+            .map_err(CredentialsError::provider_error)?;
+        let output = self
+            .client
+            .assume_role_with_web_identity()
+            .role_arn(&self.web_identity.role_arn)
+            .role_session_name("aws-rds-signer")
+            .web_identity_token(token.trim())
+            .send()
+            .await
+            .map_err(CredentialsError::provider_error)?;
+        sts_credentials(output.credentials, "WebIdentityToken")
+    }
+}
+
+impl ProvideCredentials for WebIdentityProvider {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(self.credentials())
+    }
+}
+
+/// Extracts the AWS credentials of a resolved identity.
+pub(crate) fn from_identity(identity: &Identity) -> Result<Credentials, crate::Error> {
+    identity.data::<Credentials>().cloned().ok_or_else(|| {
+        crate::Error::SignerError("the identity does not hold AWS credentials".to_string())
+    })
+}
+
+/// Builds a credentials provider for a named profile in the AWS config files.
+///
+/// Static keys, `role_arn` with `source_profile` (including nested chains), and
+/// `credential_process` entries are all handled by the profile provider.
+/// SSO-backed profiles read the SSO token cache and refresh near-expiry tokens
+/// through the profile's `sso-session`.
+///
+/// When `process_timeout` is set and the profile obtains its credentials from
+/// `credential_process`, directly or at the end of a `source_profile` chain,
+/// the signer runs the helper itself so that it is killed once the timeout
+/// elapses, and assumes the chained roles through `sts_config`. STS and SSO
+/// requests use `http_client` when set, and the endpoint variants of `sts_config`.
+/// The `credential_process` chain is read through `fs` and `env`.
+pub(crate) async fn profile_provider(
+    profile: &str,
+    region: &str,
+    process_timeout: Option<Duration>,
+    http_client: Option<&SharedHttpClient>,
+    sts_config: &SdkConfig,
+    fs: &Fs,
+    env: &Env,
+) -> SharedCredentialsProvider {
+    if let Some(timeout) = process_timeout {
+        if let Some(chain) = profile_process_chain(profile, fs, env).await {
+            let mut provider = SharedCredentialsProvider::new(CredentialProcess::new(
+                chain.command,
+                Some(timeout),
+            ));
+            for role in &chain.roles {
+                provider = role.provider(sts_config, provider).await;
+            }
+            return provider;
+        }
+    }
+    let provider_config = provider_config(
+        Some(Region::new(region.to_string())),
+        http_client,
+        EndpointVariants {
+            fips: sts_config.use_fips() == Some(true),
+            dual_stack: sts_config.use_dual_stack() == Some(true),
+        },
+    );
+    SharedCredentialsProvider::new(
+        ProfileFileCredentialsProvider::builder()
+            .configure(&provider_config)
+            .profile_name(profile)
+            .build(),
+    )
+}
+
+/// Configures `loader` with credential and region chains that skip the EC2 metadata service.
+///
+/// The chains mirror the defaults (environment, profile, web identity, and
+/// container credentials) without the IMDS fallback, which otherwise adds
+/// seconds of timeouts outside EC2. With `fail_fast`, a failing chain reports
+/// why each provider could not supply credentials.
+pub(crate) async fn without_imds(
+    loader: ConfigLoader,
+    profile: Option<&str>,
+    http_client: Option<&SharedHttpClient>,
+    endpoint_variants: EndpointVariants,
+    fail_fast: bool,
+) -> ConfigLoader {
+    let mut profile_region = ProfileFileRegionProvider::builder();
+    let mut profile_credentials = ProfileFileCredentialsProvider::builder();
+    if let Some(profile) = profile {
+        profile_region = profile_region.profile_name(profile);
+        profile_credentials = profile_credentials.profile_name(profile);
+    }
+    let region = RegionProviderChain::first_try(EnvironmentVariableRegionProvider::new())
+        .or_else(profile_region.build())
+        .region()
+        .await;
+    let provider_config = provider_config(region.clone(), http_client, endpoint_variants);
+    let providers = vec![
+        (
+            "Environment",
+            SharedCredentialsProvider::new(EnvironmentVariableCredentialsProvider::new()),
+        ),
+        (
+            "Profile",
+            SharedCredentialsProvider::new(profile_credentials.configure(&provider_config).build()),
+        ),
+        (
+            "WebIdentityToken",
+            SharedCredentialsProvider::new(
+                WebIdentityTokenCredentialsProvider::builder()
+                    .configure(&provider_config)
+                    .build(),
+            ),
+        ),
+        (
+            "EcsContainer",
+            SharedCredentialsProvider::new(
+                EcsCredentialsProvider::builder()
+                    .configure(&provider_config)
+                    .build(),
+            ),
+        ),
+    ];
+    // Without a region, the default bearer token chain looks one up through
+    // IMDS; the signer never uses bearer tokens, so only the profile's is kept.
+    let mut profile_token = ProfileFileTokenProvider::builder();
+    if let Some(profile) = profile {
+        profile_token = profile_token.profile_name(profile);
+    }
+    let loader = loader.region(region).token_provider(profile_token.build());
+    if fail_fast {
+        return loader.credentials_provider(ReportingChain { providers });
+    }
+    let mut providers = providers.into_iter();
+    let (name, first) = providers.next().expect("the chain has providers");
+    let chain = providers.fold(
+        CredentialsProviderChain::first_try(name, first),
+        |chain, (name, provider)| chain.or_else(name, provider),
+    );
+    loader.credentials_provider(chain)
+}
+
+/// A credentials chain that reports why every provider failed to supply credentials.
+///
+/// Like the default chain, it moves on to the next provider when one is not
+/// configured and stops at the first provider that fails outright.
+#[derive(Debug)]
+pub(crate) struct ReportingChain {
+    /// The providers to try in order, with their names.
+    pub(crate) providers: Vec<(&'static str, SharedCredentialsProvider)>,
+}
+
+impl ReportingChain {
+    /// Tries each provider, collecting the reason each one was skipped.
+    async fn credentials(&self) -> provider::Result {
+        let mut skipped = Vec::with_capacity(self.providers.len());
+        for (name, provider) in &self.providers {
+            match provider.provide_credentials().await {
+                Ok(credentials) => return Ok(credentials),
+                Err(error @ CredentialsError::CredentialsNotLoaded(_)) => {
+                    skipped.push(format!("{name} ({})", not_loaded_reason(&error)));
+                }
+                Err(error) => return Err(error),
+            }
+        }
+        Err(CredentialsError::not_loaded(format!(
+            "no credentials found; tried {}",
+            skipped.join(", ")
+        )))
+    }
+}
+
+impl ProvideCredentials for ReportingChain {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(self.credentials())
+    }
+}
+
+/// Builds the configuration for credential providers created by the signer.
+fn provider_config(
+    region: Option<Region>,
+    http_client: Option<&SharedHttpClient>,
+    endpoint_variants: EndpointVariants,
+) -> ProviderConfig {
+    let config = ProviderConfig::without_region()
+        .with_region(region)
+        .with_use_fips(endpoint_variants.fips.then_some(true))
+        .with_use_dual_stack(endpoint_variants.dual_stack.then_some(true));
+    match http_client {
+        Some(http_client) => config.with_http_client(http_client.clone()),
+        None => config,
+    }
+}
+
+/// A profile whose credentials come from `credential_process`, possibly through
+/// a chain of `role_arn` and `source_profile` entries.
+#[derive(Debug)]
+pub(crate) struct ProfileProcessChain {
+    /// The `credential_process` command of the profile at the end of the chain.
+    pub(crate) command: String,
+    /// The roles to assume with the process credentials, in order.
+    pub(crate) roles: Vec<AssumeRole>,
+}
+
+/// Follows the `source_profile` chain of `profile` to a `credential_process` entry.
+///
+/// Returns `None` when the chain ends in any other credential source, uses MFA,
+/// or is cyclic, leaving those profiles to the profile provider.
+pub(crate) async fn profile_process_chain(
+    profile: &str,
+    fs: &Fs,
+    env: &Env,
+) -> Option<ProfileProcessChain> {
+    let profiles = aws_config::profile::load(
+        fs,
+        env,
+        &EnvConfigFiles::default(),
+        Some(Cow::Owned(profile.to_string())),
+    )
+    .await
+    .ok()?;
+    let mut roles = Vec::new();
+    let mut visited = Vec::new();
+    let mut name = profile;
+    loop {
+        if visited.contains(&name) {
+            return None;
+        }
+        visited.push(name);
+        let current = profiles.get_profile(name)?;
+        let Some(role_arn) = current.get("role_arn") else {
+            roles.reverse();
+            return Some(ProfileProcessChain {
+                command: current.get("credential_process")?.to_string(),
+                roles,
+            });
+        };
+        if current.get("mfa_serial").is_some() {
+            return None;
+        }
+        let mut role = AssumeRole::new(role_arn);
+        if let Some(external_id) = current.get("external_id") {
+            role = role.external_id(external_id);
+        }
+        if let Some(session_name) = current.get("role_session_name") {
+            role = role.session_name(session_name);
+        }
+        roles.push(role);
+        name = current.get("source_profile")?;
+    }
+}
+
+/// Converts a credential resolution failure into a crate error.
+///
+/// When `sso_profile` is set, provider failures are reported as an expired or
+/// missing SSO session with a hint to log in again. Credentials that no provider
+/// could supply are reported as a `NoCredentialsError`.
+pub(crate) fn resolution_error(
+    error: &CredentialsError,
+    sso_profile: Option<&str>,
+) -> crate::Error {
+    if let CredentialsError::CredentialsNotLoaded(_) = error {
+        return crate::Error::NoCredentialsError(not_loaded_reason(error));
+    }
+    let message = error_chain(error);
+    match (error, sso_profile) {
+        (CredentialsError::ProviderError(_), Some(profile)) => crate::Error::SsoSessionError(
+            format!("{message}; run `aws sso login --profile {profile}` to start a new session"),
+        ),
+        _ => crate::Error::SignerError(message),
+    }
+}
+
+/// Renders the reason a provider did not load credentials, without the generic top-level message.
+fn not_loaded_reason(error: &CredentialsError) -> String {
+    std::error::Error::source(error).map_or_else(|| error_chain(error), error_chain)
+}
+
+/// Renders an error and all of its sources as a single message.
+pub(crate) fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(error) = source {
+        message.push_str(": ");
+        message.push_str(&error.to_string());
+        source = error.source();
+    }
+    message
+}
@@ -0,0 +1,159 @@
+//! A common interface for database password sources.
+//!
+//! [`DatabaseCredentials`] is implemented by [`Signer`], which generates IAM
+//! authentication tokens, and by [`StaticPassword`] for databases with a
+//! fixed password, so applications can choose the authentication mode per
+//! environment and hold a `Box<dyn DatabaseCredentials>`:
+//!
+//! ```rust,no_run
+//! # use aws_rds_signer::{DatabaseCredentials, Signer, StaticPassword};
+//! # async fn connect(local: bool) -> Result<(), aws_rds_signer::Error> {
+//! let credentials: Box<dyn DatabaseCredentials> = if local {
+//!     Box::new(StaticPassword::new("postgres"))
+//! } else {
+//!     Box::new(Signer::from_env()?)
+//! };
+//! let password = credentials.password().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::SystemTime;
+
+use crate::sign::{AuthToken, Signer};
+
+/// The future returned by [`DatabaseCredentials::password`].
+pub type PasswordFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Password, crate::Error>> + Send + 'a>>;
+
+/// A source of passwords for connecting to a database.
+///
+/// # Example
+///
+/// ```rust
+/// use aws_rds_signer::{DatabaseCredentials, Password, PasswordFuture};
+///
+/// #[derive(Debug)]
+/// struct Vault;
+///
+/// impl DatabaseCredentials for Vault {
+///     fn password(&self) -> PasswordFuture<'_> {
+///         Box::pin(async { Ok(Password::new("from-vault")) })
+///     }
+/// }
+/// ```
+pub trait DatabaseCredentials: Send + Sync + fmt::Debug {
+    /// Returns the password to connect with.
+    ///
+    /// Call it for every new connection; sources with expiring passwords
+    /// return a fresh one when needed.
+    fn password(&self) -> PasswordFuture<'_>;
+}
+
+/// A database password returned by a [`DatabaseCredentials`] source.
+///
+/// The `Debug` output omits the password.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Password {
+    /// The password.
+    value: String,
+    /// The time after which the password is no longer accepted, if it expires.
+    expires_at: Option<SystemTime>,
+}
+
+impl Password {
+    /// Creates a password that does not expire.
+    #[must_use]
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            expires_at: None,
+        }
+    }
+
+    /// Creates a password that is no longer accepted after `expires_at`.
+    #[must_use]
+    pub fn expiring(value: impl Into<String>, expires_at: SystemTime) -> Self {
+        Self {
+            value: value.into(),
+            expires_at: Some(expires_at),
+        }
+    }
+
+    /// Returns the password.
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the time after which the password is no longer accepted, if it expires.
+    #[must_use]
+    pub const fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+
+    /// Consumes the password and returns its value.
+    #[must_use]
+    pub fn into_value(mut self) -> String {
+        std::mem::take(&mut self.value)
+    }
+}
+
+impl fmt::Debug for Password {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Password")
+            .field("value", &"** redacted **")
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Password {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.value);
+    }
+}
+
+impl From<AuthToken> for Password {
+    fn from(token: AuthToken) -> Self {
+        let expires_at = token.expires_at();
+        Self::expiring(token.into_value(), expires_at)
+    }
+}
+
+impl DatabaseCredentials for Signer {
+    /// Generates a token with [`Signer::fetch_token`] and returns it as the password.
+    fn password(&self) -> PasswordFuture<'_> {
+        Box::pin(async { self.fetch_token().await.map(Password::from) })
+    }
+}
+
+/// A fixed database password, for environments without IAM authentication.
+#[derive(Clone, PartialEq, Eq)]
+pub struct StaticPassword(Password);
+
+impl StaticPassword {
+    /// Creates a source that always returns `password`.
+    #[must_use]
+    pub fn new(password: impl Into<String>) -> Self {
+        Self(Password::new(password))
+    }
+}
+
+impl fmt::Debug for StaticPassword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StaticPassword")
+            .field(&"** redacted **")
+            .finish()
+    }
+}
+
+impl DatabaseCredentials for StaticPassword {
+    fn password(&self) -> PasswordFuture<'_> {
+        Box::pin(std::future::ready(Ok(self.0.clone())))
+    }
+}
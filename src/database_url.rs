@@ -0,0 +1,234 @@
+//! Extracts connection settings from database URLs.
+//!
+//! Services are often configured with a single `DATABASE_URL` such as
+//! `postgres://iam_user@mydb.xxxx.us-east-1.rds.amazonaws.com:5432/app`; this
+//! module splits it into the host, port, user, and region the signer needs,
+//! and the database name and parameters needed to connect.
+//! It also parses compact signer specs such as
+//! `iam_user@mydb.xxxx.us-east-1.rds.amazonaws.com:5432?region=us-east-1&expires=600`.
+
+use std::net::Ipv6Addr;
+use std::time::Duration;
+
+use crate::engine::Engine;
+use crate::sign::Signer;
+
+/// The parts of a database URL that the signer does not use.
+///
+/// Returned by [`parse_database_url`] so that the final connection string can
+/// be assembled from these parts and a freshly generated token. The password
+/// of the URL, if any, is not kept, since the token replaces it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DbParts {
+    /// The percent-decoded database name from the URL path, if present.
+    pub dbname: Option<String>,
+    /// The `sslmode` (or `MySQL` `ssl-mode`) parameter, if present.
+    pub sslmode: Option<String>,
+    /// The remaining query parameters, in order.
+    pub options: Vec<(String, String)>,
+}
+
+/// The connection settings found in a database URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DatabaseUrl {
+    /// The database hostname.
+    pub(crate) host: String,
+    /// The port, or the default port of the URL scheme.
+    pub(crate) port: u16,
+    /// The percent-decoded user name, if present.
+    pub(crate) user: Option<String>,
+    /// The region inferred from an RDS endpoint hostname, if any.
+    pub(crate) region: Option<String>,
+    /// The engine named by the URL scheme.
+    pub(crate) engine: Engine,
+    /// The database name and parameters.
+    pub(crate) parts: DbParts,
+}
+
+impl DatabaseUrl {
+    /// Parses a `postgres`, `postgresql`, `mysql`, or `mariadb` URL.
+    pub(crate) fn parse(database_url: &str) -> Result<Self, crate::Error> {
+        let url =
+            url::Url::parse(database_url).map_err(|e| crate::Error::ParseError(e.to_string()))?;
+        let engine = Engine::from_url_scheme(url.scheme()).ok_or_else(|| {
+            crate::Error::ParseError(format!(
+                "unsupported database URL scheme `{}`",
+                url.scheme()
+            ))
+        })?;
+        let host = match url.host() {
+            Some(url::Host::Ipv6(address)) => address.to_string(),
+            Some(host) if !host.to_string().is_empty() => host.to_string(),
+            _ => {
+                return Err(crate::Error::ParseError(
+                    "database URL has no host".to_string(),
+                ))
+            }
+        };
+        let user = match url.username() {
+            "" => None,
+            user => Some(
+                percent_encoding::percent_decode_str(user)
+                    .decode_utf8()
+                    .map_err(|e| crate::Error::ParseError(e.to_string()))?
+                    .into_owned(),
+            ),
+        };
+        let dbname = percent_encoding::percent_decode_str(url.path().trim_start_matches('/'))
+            .decode_utf8()
+            .map_err(|e| crate::Error::ParseError(e.to_string()))?;
+        let mut parts = DbParts {
+            dbname: (!dbname.is_empty()).then(|| dbname.into_owned()),
+            ..DbParts::default()
+        };
+        for (name, value) in url.query_pairs() {
+            match name.as_ref() {
+                "sslmode" | "ssl-mode" => parts.sslmode = Some(value.into_owned()),
+                _ => parts.options.push((name.into_owned(), value.into_owned())),
+            }
+        }
+        Ok(Self {
+            region: rds_region(&host),
+            port: url.port().unwrap_or_else(|| engine.default_port()),
+            engine,
+            user,
+            host,
+            parts,
+        })
+    }
+}
+
+/// Creates a signer from a database URL and returns it with the rest of the URL.
+///
+/// The signer is created as with [`Signer::from_url`]; the database name,
+/// `sslmode`, and other query parameters are returned as [`DbParts`] so the
+/// connection string can be rebuilt around a generated token.
+///
+/// # Arguments
+/// * `database_url` - The URL (e.g., `postgres://iam_user@mydb.xxxx.us-east-1.rds.amazonaws.com/app?sslmode=verify-full`)
+///
+/// # Errors
+/// Returns the same errors as [`Signer::from_url`].
+pub fn parse_database_url(database_url: &str) -> Result<(Signer, DbParts), crate::Error> {
+    let mut database_url = DatabaseUrl::parse(database_url)?;
+    let parts = std::mem::take(&mut database_url.parts);
+    Ok((Signer::from_database_url(database_url)?, parts))
+}
+
+/// The settings found in a compact signer spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Spec {
+    /// The database hostname.
+    pub(crate) host: String,
+    /// The port, if present.
+    pub(crate) port: Option<u16>,
+    /// The percent-decoded user name.
+    pub(crate) user: String,
+    /// The `region` query parameter, if present.
+    pub(crate) region: Option<String>,
+    /// The `expires` query parameter in seconds, if present.
+    pub(crate) expires_in: Option<Duration>,
+}
+
+impl Spec {
+    /// Parses a spec of the form `user@host[:port][?region=...&expires=...]`.
+    pub(crate) fn parse(spec: &str) -> Result<Self, crate::Error> {
+        let error = |reason: &str| {
+            crate::Error::ParseError(format!("invalid signer spec `{spec}`: {reason}"))
+        };
+        if spec.contains("://") {
+            return Err(error("expected `user@host:port`, not a URL"));
+        }
+        let url = url::Url::parse(&format!("rds://{spec}")).map_err(|e| error(&e.to_string()))?;
+        let host = match url.host() {
+            Some(url::Host::Ipv6(address)) => address.to_string(),
+            Some(host) if !host.to_string().is_empty() => host.to_string(),
+            _ => return Err(error("missing host")),
+        };
+        if !matches!(url.path(), "" | "/") {
+            return Err(error("unexpected path"));
+        }
+        let user = percent_encoding::percent_decode_str(url.username())
+            .decode_utf8()
+            .map_err(|e| error(&e.to_string()))?
+            .into_owned();
+        if user.is_empty() {
+            return Err(error("missing user"));
+        }
+        let mut parsed = Self {
+            host,
+            port: url.port(),
+            user,
+            region: None,
+            expires_in: None,
+        };
+        for (name, value) in url.query_pairs() {
+            match name.as_ref() {
+                "region" => parsed.region = Some(value.into_owned()),
+                "expires" => {
+                    let seconds = value
+                        .parse::<u64>()
+                        .map_err(|_| error("`expires` must be a number of seconds"))?;
+                    parsed.expires_in = Some(Duration::from_secs(seconds));
+                }
+                name => return Err(error(&format!("unknown parameter `{name}`"))),
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// Splits a `host:port` string, accepting bracketed IPv6 addresses such as `[::1]:5432`.
+pub(crate) fn parse_host_and_port(host_and_port: &str) -> Result<(String, u16), crate::Error> {
+    let error = |reason: &str| {
+        crate::Error::ParseError(format!("invalid host and port `{host_and_port}`: {reason}"))
+    };
+    let (host, port) = if let Some(rest) = host_and_port.strip_prefix('[') {
+        let (address, port) = rest
+            .split_once("]:")
+            .ok_or_else(|| error("expected `[address]:port`"))?;
+        address
+            .parse::<Ipv6Addr>()
+            .map_err(|_| error("not an IPv6 address"))?;
+        (address, port)
+    } else {
+        let (host, port) = host_and_port
+            .rsplit_once(':')
+            .ok_or_else(|| error("missing port"))?;
+        if host.contains(':') {
+            return Err(error("IPv6 addresses must be enclosed in brackets"));
+        }
+        (host, port)
+    };
+    if host.is_empty() {
+        return Err(error("missing host"));
+    }
+    let port = port
+        .parse::<u16>()
+        .ok()
+        .filter(|port| *port != 0)
+        .ok_or_else(|| error("port must be a number from 1 to 65535"))?;
+    Ok((host.to_string(), port))
+}
+
+/// Splits a port off a host such as `mydb:5432` or `[fd00::1]:5432`, if one is embedded.
+pub(crate) fn split_port(host: &str) -> Option<(String, u16)> {
+    if !host.contains(':') || host.parse::<Ipv6Addr>().is_ok() {
+        return None;
+    }
+    parse_host_and_port(host).ok()
+}
+
+/// Returns the region of an RDS endpoint such as `mydb.xxxx.us-east-1.rds.amazonaws.com`
+/// or, in the China regions, `mydb.xxxx.rds.cn-north-1.amazonaws.com.cn`.
+pub(crate) fn rds_region(host: &str) -> Option<String> {
+    let labels: Vec<&str> = host.split('.').collect();
+    // The service label is searched from the end, as a database may be named `rds`.
+    let rds = labels.iter().rposition(|label| *label == "rds")?;
+    let region = match &labels[rds + 1..] {
+        [region, "amazonaws", "com", "cn"] => region,
+        ["amazonaws", "com"] | ["amazonaws", "com", "cn"] if rds > 0 => labels[rds - 1],
+        _ => return None,
+    };
+    crate::validation::is_region(region).then(|| region.to_string())
+}
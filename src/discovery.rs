@@ -0,0 +1,242 @@
+//! Endpoint discovery with the RDS API.
+//!
+//! [`Signer::for_db_instance`] looks up the endpoint of a DB instance with
+//! `DescribeDBInstances`, and [`Signer::for_db_cluster`] the writer, reader, or
+//! a custom endpoint of an Aurora cluster, so endpoints need not be copied into
+//! configuration:
+//!
+//! ```rust,no_run
+//! # use aws_rds_signer::{EndpointType, Signer};
+//! # async fn discover() -> Result<(), aws_rds_signer::Error> {
+//! let signer = Signer::for_db_instance("prod-db").await?.user("app").build();
+//! let reader = Signer::for_db_cluster("prod-cluster", EndpointType::Reader)
+//!     .await?
+//!     .user("app")
+//!     .build();
+//! # Ok(())
+//! # }
+//! ```
+
+use aws_config::SdkConfig;
+use aws_sdk_rds::Client;
+use aws_smithy_types::error::display::DisplayErrorContext;
+
+use crate::engine::Engine;
+use crate::sign::{Signer, SignerBuilder};
+
+/// The endpoint of an Aurora DB cluster to sign tokens for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EndpointType {
+    /// The cluster endpoint, which connects to the current writer instance.
+    Writer,
+    /// The reader endpoint, which load-balances across the replicas.
+    Reader,
+    /// A custom endpoint, by its identifier (e.g., "analytics").
+    Custom(String),
+}
+
+impl Signer {
+    /// Creates a builder for the DB instance `identifier`, using the default AWS configuration.
+    ///
+    /// Equivalent to [`Self::for_db_instance_with_config`] with the configuration
+    /// loaded by `aws_config::load_defaults`.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::for_db_instance_with_config`].
+    pub async fn for_db_instance(identifier: &str) -> Result<SignerBuilder, crate::Error> {
+        let sdk_config = aws_config::load_defaults(crate::sign::behavior_version()).await;
+        Self::for_db_instance_with_config(&sdk_config, identifier).await
+    }
+
+    /// Creates a builder for the DB instance `identifier` by calling `DescribeDBInstances`.
+    ///
+    /// The builder is seeded from `sdk_config` as with [`Self::from_sdk_config`],
+    /// and signs for the instance's endpoint address, port, engine, and the
+    /// region of its ARN. Set the database user on the returned builder.
+    ///
+    /// # Arguments
+    /// * `sdk_config` - The AWS configuration used for the RDS API and for signing
+    /// * `identifier` - The DB instance identifier (e.g., "prod-db")
+    ///
+    /// # Errors
+    /// * `DiscoveryError` - If the API call fails, or the instance does not exist or has no endpoint yet
+    pub async fn for_db_instance_with_config(
+        sdk_config: &SdkConfig,
+        identifier: &str,
+    ) -> Result<SignerBuilder, crate::Error> {
+        let output = Client::new(sdk_config)
+            .describe_db_instances()
+            .db_instance_identifier(identifier)
+            .send()
+            .await
+            .map_err(|e| {
+                crate::Error::DiscoveryError(format!(
+                    "DescribeDBInstances for `{identifier}` failed: {}",
+                    DisplayErrorContext(e)
+                ))
+            })?;
+        let instance = output.db_instances().first().ok_or_else(|| {
+            crate::Error::DiscoveryError(format!("DB instance `{identifier}` was not found"))
+        })?;
+        let (address, port) = instance
+            .endpoint()
+            .and_then(|endpoint| Some((endpoint.address()?, endpoint.port())))
+            .ok_or_else(|| {
+                crate::Error::DiscoveryError(format!(
+                    "DB instance `{identifier}` has no endpoint yet"
+                ))
+            })?;
+        if instance.iam_database_authentication_enabled() == Some(false) {
+            tracing::warn!(
+                identifier,
+                "IAM database authentication is disabled for the DB instance; tokens will be rejected"
+            );
+        }
+
+        Ok(discovered(
+            sdk_config,
+            address,
+            port,
+            instance.engine(),
+            instance.db_instance_arn(),
+        ))
+    }
+
+    /// Creates a builder for an endpoint of the DB cluster `identifier`, using the default AWS configuration.
+    ///
+    /// Equivalent to [`Self::for_db_cluster_with_config`] with the configuration
+    /// loaded by `aws_config::load_defaults`.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::for_db_cluster_with_config`].
+    pub async fn for_db_cluster(
+        identifier: &str,
+        endpoint_type: EndpointType,
+    ) -> Result<SignerBuilder, crate::Error> {
+        let sdk_config = aws_config::load_defaults(crate::sign::behavior_version()).await;
+        Self::for_db_cluster_with_config(&sdk_config, identifier, endpoint_type).await
+    }
+
+    /// Creates a builder for an endpoint of the DB cluster `identifier` by calling `DescribeDBClusters`.
+    ///
+    /// Custom endpoints are additionally looked up with `DescribeDBClusterEndpoints`.
+    /// The builder is seeded as with [`Self::for_db_instance_with_config`], with
+    /// the cluster's port, engine, and region. Discover the endpoint again after
+    /// topology changes, such as when a custom endpoint is recreated.
+    ///
+    /// # Arguments
+    /// * `sdk_config` - The AWS configuration used for the RDS API and for signing
+    /// * `identifier` - The DB cluster identifier (e.g., "prod-cluster")
+    /// * `endpoint_type` - The writer, reader, or a custom endpoint of the cluster
+    ///
+    /// # Errors
+    /// * `DiscoveryError` - If an API call fails, or the cluster or endpoint does not exist
+    pub async fn for_db_cluster_with_config(
+        sdk_config: &SdkConfig,
+        identifier: &str,
+        endpoint_type: EndpointType,
+    ) -> Result<SignerBuilder, crate::Error> {
+        let client = Client::new(sdk_config);
+        let output = client
+            .describe_db_clusters()
+            .db_cluster_identifier(identifier)
+            .send()
+            .await
+            .map_err(|e| {
+                crate::Error::DiscoveryError(format!(
+                    "DescribeDBClusters for `{identifier}` failed: {}",
+                    DisplayErrorContext(e)
+                ))
+            })?;
+        let cluster = output.db_clusters().first().ok_or_else(|| {
+            crate::Error::DiscoveryError(format!("DB cluster `{identifier}` was not found"))
+        })?;
+        if cluster.iam_database_authentication_enabled() == Some(false) {
+            tracing::warn!(
+                identifier,
+                "IAM database authentication is disabled for the DB cluster; tokens will be rejected"
+            );
+        }
+
+        let address = match &endpoint_type {
+            EndpointType::Writer => cluster.endpoint().map(ToString::to_string),
+            EndpointType::Reader => cluster.reader_endpoint().map(ToString::to_string),
+            EndpointType::Custom(endpoint) => client
+                .describe_db_cluster_endpoints()
+                .db_cluster_identifier(identifier)
+                .db_cluster_endpoint_identifier(endpoint)
+                .send()
+                .await
+                .map_err(|e| {
+                    crate::Error::DiscoveryError(format!(
+                        "DescribeDBClusterEndpoints for `{identifier}` failed: {}",
+                        DisplayErrorContext(e)
+                    ))
+                })?
+                .db_cluster_endpoints()
+                .first()
+                .and_then(|endpoint| endpoint.endpoint())
+                .map(ToString::to_string),
+        }
+        .ok_or_else(|| {
+            crate::Error::DiscoveryError(format!(
+                "DB cluster `{identifier}` has no {endpoint_type} endpoint"
+            ))
+        })?;
+        Ok(discovered(
+            sdk_config,
+            &address,
+            cluster.port(),
+            cluster.engine(),
+            cluster.db_cluster_arn(),
+        ))
+    }
+}
+
+impl std::fmt::Display for EndpointType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Writer => f.write_str("writer"),
+            Self::Reader => f.write_str("reader"),
+            Self::Custom(endpoint) => write!(f, "custom `{endpoint}`"),
+        }
+    }
+}
+
+/// Returns a builder seeded from `sdk_config` for a discovered endpoint.
+fn discovered(
+    sdk_config: &SdkConfig,
+    address: &str,
+    port: Option<i32>,
+    engine: Option<&str>,
+    arn: Option<&str>,
+) -> SignerBuilder {
+    let mut builder = Signer::from_sdk_config(sdk_config).host(address);
+    if let Some(engine) = engine.and_then(engine_from_rds) {
+        builder = builder.engine(engine);
+    }
+    if let Some(port) = port.and_then(|port| u16::try_from(port).ok()) {
+        builder = builder.port(port);
+    }
+    if let Some(region) = arn.and_then(arn_region) {
+        builder = builder.region(region);
+    }
+    builder
+}
+
+/// Returns the engine for an RDS engine name such as `aurora-postgresql`.
+fn engine_from_rds(engine: &str) -> Option<Engine> {
+    match engine {
+        "postgres" => Some(Engine::Postgres),
+        "mysql" => Some(Engine::MySql),
+        "mariadb" => Some(Engine::MariaDb),
+        "aurora-postgresql" => Some(Engine::AuroraPostgres),
+        "aurora-mysql" | "aurora" => Some(Engine::AuroraMySql),
+        _ => None,
+    }
+}
+
+/// Returns the region of an ARN such as `arn:aws:rds:us-east-1:123456789012:db:prod-db`.
+fn arn_region(arn: &str) -> Option<&str> {
+    arn.split(':').nth(3).filter(|region| !region.is_empty())
+}
@@ -0,0 +1,70 @@
+//! Database engines supported by RDS IAM authentication.
+
+use std::fmt;
+
+/// The database engine of an RDS instance or Aurora cluster.
+///
+/// The engine determines the default port and the URL scheme of connection
+/// strings. IAM authentication requires TLS for every engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Engine {
+    /// RDS for `PostgreSQL`.
+    Postgres,
+    /// RDS for `MySQL`.
+    MySql,
+    /// RDS for `MariaDB`.
+    MariaDb,
+    /// Aurora `PostgreSQL`-Compatible Edition.
+    AuroraPostgres,
+    /// Aurora `MySQL`-Compatible Edition.
+    AuroraMySql,
+}
+
+impl Engine {
+    /// Returns the default port of the engine: 5432 for `PostgreSQL`, 3306 otherwise.
+    #[must_use]
+    pub const fn default_port(self) -> u16 {
+        match self {
+            Self::Postgres | Self::AuroraPostgres => 5432,
+            Self::MySql | Self::MariaDb | Self::AuroraMySql => 3306,
+        }
+    }
+
+    /// Returns the URL scheme used in connection strings for the engine.
+    #[must_use]
+    pub const fn url_scheme(self) -> &'static str {
+        match self {
+            Self::Postgres | Self::AuroraPostgres => "postgres",
+            Self::MySql | Self::AuroraMySql => "mysql",
+            Self::MariaDb => "mariadb",
+        }
+    }
+
+    /// Returns whether the engine speaks the `PostgreSQL` wire protocol.
+    #[must_use]
+    pub const fn is_postgres(self) -> bool {
+        matches!(self, Self::Postgres | Self::AuroraPostgres)
+    }
+
+    /// Returns the engine for a connection string scheme, if known.
+    pub(crate) fn from_url_scheme(scheme: &str) -> Option<Self> {
+        match scheme {
+            "postgres" | "postgresql" => Some(Self::Postgres),
+            "mysql" => Some(Self::MySql),
+            "mariadb" => Some(Self::MariaDb),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Engine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Postgres => "postgres",
+            Self::MySql => "mysql",
+            Self::MariaDb => "mariadb",
+            Self::AuroraPostgres => "aurora-postgresql",
+            Self::AuroraMySql => "aurora-mysql",
+        })
+    }
+}
@@ -0,0 +1,50 @@
+//! Integration with the `figment` configuration library.
+//!
+//! A [`SignerConfig`] is a figment [`Provider`], so signer defaults can be
+//! layered under environment variables and configuration files, e.g.
+//! figment's `Env::prefixed("RDS_SIGNER_")`:
+//!
+//! ```rust,no_run
+//! # use aws_rds_signer::{Signer, SignerConfig};
+//! # use figment::{providers::Serialized, Figment};
+//! # fn load() -> Result<(), aws_rds_signer::Error> {
+//! let figment = Figment::from(SignerConfig::new("mydb.xxxx.us-east-1.rds.amazonaws.com", "app"))
+//!     .merge(Serialized::default("port", 6432));
+//! let signer = Signer::from_figment(&figment)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use figment::providers::Serialized;
+use figment::value::{Dict, Map};
+use figment::{Error, Figment, Metadata, Profile, Provider};
+
+use crate::sign::Signer;
+use crate::signer_config::SignerConfig;
+
+impl Provider for SignerConfig {
+    fn metadata(&self) -> Metadata {
+        Metadata::named("aws-rds-signer configuration")
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        Serialized::defaults(self).data()
+    }
+}
+
+impl Signer {
+    /// Creates a signer from the [`SignerConfig`] extracted from `figment`.
+    ///
+    /// Use `figment.focus("database")` to read settings nested under a key.
+    /// The result is validated with [`SignerBuilder::try_build`](crate::SignerBuilder::try_build).
+    ///
+    /// # Errors
+    /// * `ParseError` - If the settings cannot be extracted
+    /// * `ValidationError` - If the configuration is invalid
+    pub fn from_figment(figment: &Figment) -> Result<Self, crate::Error> {
+        let config: SignerConfig = figment
+            .extract()
+            .map_err(|e| crate::Error::ParseError(e.to_string()))?;
+        Self::from_config(&config)
+    }
+}
@@ -0,0 +1,101 @@
+//! Parses JSON credential documents.
+//!
+//! Both `credential_process` helpers and container credential endpoints return
+//! credentials as a flat JSON object; this module extracts the common fields.
+
+use std::time::SystemTime;
+
+use aws_credential_types::provider::{self, error::CredentialsError};
+use aws_credential_types::Credentials;
+use aws_smithy_json::deserialize::{json_token_iter, Token};
+use aws_smithy_types::date_time::Format;
+use aws_smithy_types::{DateTime, Number};
+
+/// The fields of a JSON credentials document.
+#[derive(Debug, Default)]
+pub(crate) struct JsonCredentials {
+    /// The document format version, present in `credential_process` output.
+    pub(crate) version: Option<Number>,
+    /// The access key ID.
+    access_key_id: Option<String>,
+    /// The secret access key.
+    secret_access_key: Option<String>,
+    /// The session token, named `SessionToken` or `Token` depending on the source.
+    session_token: Option<String>,
+    /// The RFC 3339 expiration timestamp.
+    expiration: Option<String>,
+}
+
+impl JsonCredentials {
+    /// Parses the top-level fields of a JSON credentials document.
+    pub(crate) fn parse(document: &[u8]) -> Result<Self, CredentialsError> {
+        let mut fields = Self::default();
+        let mut tokens = json_token_iter(document);
+        let mut depth = 0usize;
+        while let Some(token) = tokens.next() {
+            match token.map_err(CredentialsError::unhandled)? {
+                Token::StartObject { .. } | Token::StartArray { .. } => depth += 1,
+                Token::EndObject { .. } | Token::EndArray { .. } => depth -= 1,
+                Token::ObjectKey { key, .. } if depth == 1 => {
+                    let key = key.to_unescaped().map_err(CredentialsError::unhandled)?;
+                    match tokens
+                        .next()
+                        .transpose()
+                        .map_err(CredentialsError::unhandled)?
+                    {
+                        Some(Token::ValueNumber { value, .. }) if key == "Version" => {
+                            fields.version = Some(value);
+                        }
+                        Some(Token::ValueString { value, .. }) => {
+                            let value = value
+                                .to_unescaped()
+                                .map_err(CredentialsError::unhandled)?
+                                .into_owned();
+                            match key.as_ref() {
+                                "AccessKeyId" => fields.access_key_id = Some(value),
+                                "SecretAccessKey" => fields.secret_access_key = Some(value),
+                                "SessionToken" | "Token" => fields.session_token = Some(value),
+                                "Expiration" => fields.expiration = Some(value),
+                                _ => {}
+                            }
+                        }
+                        Some(Token::StartObject { .. } | Token::StartArray { .. }) => depth += 1,
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(fields)
+    }
+
+    /// Converts the parsed fields into credentials attributed to `provider_name`.
+    pub(crate) fn into_credentials(self, provider_name: &'static str) -> provider::Result {
+        let access_key_id = self
+            .access_key_id
+            .ok_or_else(|| CredentialsError::unhandled("credentials are missing `AccessKeyId`"))?;
+        let secret_access_key = self.secret_access_key.ok_or_else(|| {
+            CredentialsError::unhandled("credentials are missing `SecretAccessKey`")
+        })?;
+        let expiration = self
+            .expiration
+            .map(|expiration| {
+                DateTime::from_str(&expiration, Format::DateTimeWithOffset)
+                    .ok()
+                    .and_then(|date_time| SystemTime::try_from(date_time).ok())
+                    .ok_or_else(|| {
+                        CredentialsError::unhandled(format!(
+                            "invalid `Expiration` value: {expiration}"
+                        ))
+                    })
+            })
+            .transpose()?;
+        Ok(Credentials::new(
+            access_key_id,
+            secret_access_key,
+            self.session_token,
+            expiration,
+            provider_name,
+        ))
+    }
+}
@@ -0,0 +1,47 @@
+//! Kubernetes Secret manifests holding authentication tokens.
+
+use aws_smithy_json::serialize::JsonObjectWriter;
+
+use crate::sign::AuthToken;
+
+impl AuthToken {
+    /// Returns a Kubernetes `Secret` manifest holding the token and its connection settings.
+    ///
+    /// The manifest is JSON, which `kubectl apply -f` accepts like YAML. Its
+    /// `data` holds the base64-encoded `host`, `port`, `user`, and `password`
+    /// (the token), and the `aws-rds-signer/expires-at` annotation records
+    /// when the token expires, so operators can re-apply it before then.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the Secret
+    /// * `namespace` - The namespace of the Secret
+    #[must_use]
+    pub fn to_kubernetes_secret(&self, name: &str, namespace: &str) -> String {
+        let mut json = String::new();
+        let mut secret = JsonObjectWriter::new(&mut json);
+        secret.key("apiVersion").string("v1");
+        secret.key("kind").string("Secret");
+        let mut metadata = secret.key("metadata").start_object();
+        metadata.key("name").string(name);
+        metadata.key("namespace").string(namespace);
+        let mut annotations = metadata.key("annotations").start_object();
+        annotations
+            .key("aws-rds-signer/expires-at")
+            .string(&self.expires_at_rfc3339());
+        annotations.finish();
+        metadata.finish();
+        secret.key("type").string("Opaque");
+        let mut data = secret.key("data").start_object();
+        data.key("host")
+            .string(&aws_smithy_types::base64::encode(self.host()));
+        data.key("port")
+            .string(&aws_smithy_types::base64::encode(self.port().to_string()));
+        data.key("user")
+            .string(&aws_smithy_types::base64::encode(self.user()));
+        data.key("password")
+            .string(&aws_smithy_types::base64::encode(self.value()));
+        data.finish();
+        secret.finish();
+        json
+    }
+}
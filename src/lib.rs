@@ -3,6 +3,9 @@
 #![deny(missing_debug_implementations)]
 #![forbid(unsafe_code)]
 #![allow(clippy::multiple_crate_versions)]
+#![allow(clippy::redundant_pub_crate)]
+// `Duration::from_mins` and `from_hours` need Rust 1.91.
+#![allow(clippy::duration_suboptimal_units)]
 
 //! # AWS RDS Signer
 //!
@@ -37,12 +40,72 @@
 //! }
 //! ```
 
+#[cfg(feature = "clap")]
+mod args;
+mod batch;
+mod cache_stats;
+mod caching_signer;
+mod client_files;
+mod cluster;
+#[cfg(feature = "config-file")]
+mod config_file;
+#[cfg(feature = "config-rs")]
+mod config_rs;
+mod connection_string;
+mod container;
+mod credential_process;
+mod credential_source;
+mod credentials;
+mod database_credentials;
+mod database_url;
+#[cfg(feature = "discovery")]
+mod discovery;
+mod engine;
+#[cfg(feature = "figment")]
+mod figment_provider;
+mod json_credentials;
+mod kubernetes;
+mod refresh_schedule;
+mod refresher;
+#[cfg(feature = "secrets-manager")]
+mod secrets_manager;
 mod sign;
+mod signer_config;
+mod token_cache;
+mod token_store;
+mod validation;
+mod verify;
 
 #[cfg(test)]
 mod test;
 
-pub use sign::{Signer, SignerBuilder};
+#[cfg(feature = "clap")]
+pub use args::SignerArgs;
+pub use batch::Endpoint;
+pub use cache_stats::CacheStats;
+pub use caching_signer::CachingSigner;
+pub use cluster::ClusterSigner;
+pub use credential_source::{
+    CredentialSource, CredentialSourceError, CredentialSourceFuture, SourceCredentials,
+};
+pub use credentials::{AssumeRole, StsEndpoint};
+pub use database_credentials::{DatabaseCredentials, Password, PasswordFuture, StaticPassword};
+pub use database_url::{parse_database_url, DbParts};
+#[cfg(feature = "discovery")]
+pub use discovery::EndpointType;
+pub use engine::Engine;
+pub use refresh_schedule::RefreshSchedule;
+pub use refresher::RefresherHandle;
+#[cfg(feature = "secrets-manager")]
+pub use secrets_manager::SecretsManagerSource;
+#[allow(deprecated)]
+pub use sign::Token;
+pub use sign::{AuthToken, ExpiresInLimit, ExpiryBehavior, IntoRegion, Signer, SignerBuilder};
+pub use signer_config::SignerConfig;
+pub use token_cache::TokenCache;
+pub use token_store::{MemoryTokenStore, TokenKey, TokenStore, TokenStoreError, TokenStoreFuture};
+pub use validation::{Field, FieldError, ValidationError};
+pub use verify::{verify, verify_at, SigningDetails};
 
 /// Represents errors that can occur during the RDS signing process.
 #[derive(Debug)]
@@ -53,6 +116,28 @@ pub enum Error {
     SignerError(String),
     /// Error that occurs when retrieving environment variables.
     EnvVarError(String),
+    /// Error that occurs when the SSO session of a profile is missing or expired.
+    SsoSessionError(String),
+    /// Error that occurs when no credentials provider supplied credentials.
+    NoCredentialsError(String),
+    /// Error that occurs when the credentials expire before the requested token lifetime.
+    CredentialExpiryError(String),
+    /// Error that occurs when the signer configuration is invalid.
+    ValidationError(ValidationError),
+    /// Error that occurs when credential resolution exceeds the configured timeout.
+    TimeoutError(std::time::Duration),
+    /// Error that occurs in strict region mode when no signing region is available.
+    MissingRegion,
+    /// Error that occurs when a database endpoint cannot be discovered with the RDS API.
+    DiscoveryError(String),
+    /// Error that occurs when reading or writing a file fails.
+    IoError(String),
+    /// Error that occurs when a token fails offline verification.
+    VerificationError(String),
+    /// Error that occurs when a password cannot be read from AWS Secrets Manager.
+    SecretsManagerError(String),
+    /// Error that occurs when a token cache's storage backend fails.
+    TokenStoreError(String),
 }
 
 impl std::fmt::Display for Error {
@@ -61,6 +146,23 @@ impl std::fmt::Display for Error {
             Self::ParseError(e) => write!(f, "ParseError: {e}"),
             Self::SignerError(e) => write!(f, "SignerError: {e}"),
             Self::EnvVarError(e) => write!(f, "EnvVarError: {e}"),
+            Self::SsoSessionError(e) => write!(f, "SsoSessionError: {e}"),
+            Self::NoCredentialsError(e) => write!(f, "NoCredentialsError: {e}"),
+            Self::CredentialExpiryError(e) => write!(f, "CredentialExpiryError: {e}"),
+            Self::ValidationError(e) => write!(f, "ValidationError: {e}"),
+            Self::TimeoutError(d) => write!(
+                f,
+                "TimeoutError: credential resolution did not complete within {d:?}"
+            ),
+            Self::MissingRegion => write!(
+                f,
+                "MissingRegion: no region is configured, inferable from the host, or set in the AWS configuration"
+            ),
+            Self::DiscoveryError(e) => write!(f, "DiscoveryError: {e}"),
+            Self::IoError(e) => write!(f, "IoError: {e}"),
+            Self::VerificationError(e) => write!(f, "VerificationError: {e}"),
+            Self::SecretsManagerError(e) => write!(f, "SecretsManagerError: {e}"),
+            Self::TokenStoreError(e) => write!(f, "TokenStoreError: {e}"),
         }
     }
 }
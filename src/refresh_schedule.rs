@@ -0,0 +1,95 @@
+//! When cached and background-refreshed tokens are replaced.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime};
+
+use crate::sign::AuthToken;
+
+/// How long before expiry a token is replaced, by default.
+pub(crate) const DEFAULT_REFRESH_MARGIN: Duration = Duration::from_secs(120);
+
+/// How far ahead of expiry [`CachingSigner`](crate::CachingSigner) and
+/// [`Signer::spawn_refresher_with`](crate::Signer::spawn_refresher_with) replace tokens.
+///
+/// Each token is replaced the refresh margin before it expires, plus a random
+/// delay of up to the jitter, drawn once per token. Jitter spreads the
+/// refreshes of a fleet of instances started together, so they do not all
+/// fetch credentials from IMDS or STS at the same instant.
+///
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// # use aws_rds_signer::{RefreshSchedule, Signer};
+/// # async fn start(signer: Signer) -> Result<(), aws_rds_signer::Error> {
+/// let schedule = RefreshSchedule::new()
+///     .margin(Duration::from_secs(120))
+///     .jitter(Duration::from_secs(30));
+/// let (tokens, refresher) = signer.spawn_refresher_with(schedule).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefreshSchedule {
+    /// How long before expiry a token is replaced.
+    pub(crate) margin: Duration,
+    /// The longest random delay added to the margin.
+    pub(crate) jitter: Duration,
+}
+
+impl Default for RefreshSchedule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RefreshSchedule {
+    /// Creates a schedule that replaces tokens two minutes before they expire, without jitter.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            margin: DEFAULT_REFRESH_MARGIN,
+            jitter: Duration::ZERO,
+        }
+    }
+
+    /// Sets how long before expiry a token is replaced.
+    ///
+    /// # Arguments
+    /// * `margin` - The minimum remaining lifetime of a token when it is replaced
+    #[must_use]
+    pub const fn margin(mut self, margin: Duration) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Sets the longest random delay by which a token is replaced earlier than the margin.
+    ///
+    /// # Arguments
+    /// * `jitter` - The upper bound of the random delay; zero disables jitter
+    #[must_use]
+    pub const fn jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Returns when `token` is due for replacement: the margin plus random jitter before it expires.
+    ///
+    /// The margin is capped at `max_margin`, and the jitter at the rest of
+    /// `max_margin`, so short-lived tokens are not replaced too early.
+    pub(crate) fn refresh_at(&self, token: &AuthToken, max_margin: Duration) -> SystemTime {
+        let margin = self.margin.min(max_margin);
+        let jitter = random_duration(self.jitter.min(max_margin.saturating_sub(margin)));
+        token.refresh_after(margin.saturating_add(jitter))
+    }
+}
+
+/// Returns a random duration below `max`, or zero if `max` is zero.
+pub(crate) fn random_duration(max: Duration) -> Duration {
+    let nanos = u64::try_from(max.as_nanos()).unwrap_or(u64::MAX);
+    if nanos == 0 {
+        return Duration::ZERO;
+    }
+    // Every `RandomState` is seeded differently, which is random enough to spread refreshes.
+    let random = RandomState::new().build_hasher().finish();
+    Duration::from_nanos(random % nanos)
+}
@@ -0,0 +1,121 @@
+//! Background token refresh.
+//!
+//! [`Signer::spawn_refresher`] keeps a current token in a `tokio::sync::watch`
+//! channel, so connection pools read it without awaiting token generation
+//! when they connect:
+//!
+//! ```rust,no_run
+//! # async fn run(signer: aws_rds_signer::Signer) -> Result<(), aws_rds_signer::Error> {
+//! let (tokens, refresher) = signer.spawn_refresher().await?;
+//! // On every connect:
+//! let token = tokens.borrow().clone();
+//! // On shutdown:
+//! refresher.stop();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::{Duration, SystemTime};
+
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::refresh_schedule::RefreshSchedule;
+use crate::sign::{AuthToken, Signer};
+
+/// How long the refresher waits before retrying a failed refresh.
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A handle to a task started by [`Signer::spawn_refresher`] or [`Signer::spawn_refresher_with`].
+///
+/// Dropping the handle leaves the task running; it stops once [`Self::stop`]
+/// is called or every receiver of the channel is dropped.
+#[derive(Debug)]
+pub struct RefresherHandle {
+    /// The refresh task.
+    task: JoinHandle<()>,
+}
+
+impl RefresherHandle {
+    /// Stops the refresh task; receivers keep the last token.
+    pub fn stop(self) {
+        self.task.abort();
+    }
+
+    /// Returns whether the refresh task has stopped.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+impl Signer {
+    /// Generates a token and spawns a task that keeps replacing it before it expires.
+    ///
+    /// The returned channel holds the current token. The task mints its
+    /// replacement two minutes before expiry, or halfway through the lifetime
+    /// of shorter-lived tokens, and retries failures every five seconds while
+    /// the channel keeps the previous token. The task runs on the current
+    /// Tokio runtime and shares this signer's credentials cache.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::fetch_token`] for the first token;
+    /// later failures are logged and retried.
+    pub async fn spawn_refresher(
+        &self,
+    ) -> Result<(watch::Receiver<AuthToken>, RefresherHandle), crate::Error> {
+        self.spawn_refresher_with(RefreshSchedule::new()).await
+    }
+
+    /// Like [`Self::spawn_refresher`], but replaces tokens according to `schedule`.
+    ///
+    /// The margin and jitter together are capped at half the token lifetime.
+    ///
+    /// # Arguments
+    /// * `schedule` - How far ahead of expiry tokens are replaced
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::fetch_token`] for the first token;
+    /// later failures are logged and retried.
+    pub async fn spawn_refresher_with(
+        &self,
+        schedule: RefreshSchedule,
+    ) -> Result<(watch::Receiver<AuthToken>, RefresherHandle), crate::Error> {
+        let token = Box::pin(self.fetch_token()).await?;
+        let (sender, receiver) = watch::channel(token);
+        let task = tokio::spawn(refresh(self.clone(), schedule, sender));
+        Ok((receiver, RefresherHandle { task }))
+    }
+}
+
+/// Replaces the token in `sender` before it expires, until every receiver is dropped.
+async fn refresh(signer: Signer, schedule: RefreshSchedule, sender: watch::Sender<AuthToken>) {
+    loop {
+        let token = sender.borrow().clone();
+        let lifetime = token
+            .expires_at()
+            .duration_since(token.signed_at())
+            .unwrap_or_default();
+        let refresh_at = schedule.refresh_at(&token, lifetime / 2);
+        let delay = refresh_at
+            .duration_since(SystemTime::now())
+            .unwrap_or_default();
+        if tokio::time::timeout(delay, sender.closed()).await.is_ok() {
+            return;
+        }
+        match Box::pin(signer.fetch_token()).await {
+            Ok(token) => {
+                sender.send_replace(token);
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to refresh the RDS token; retrying");
+                if tokio::time::timeout(RETRY_INTERVAL, sender.closed())
+                    .await
+                    .is_ok()
+                {
+                    return;
+                }
+            }
+        }
+    }
+}
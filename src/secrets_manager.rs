@@ -0,0 +1,170 @@
+//! Database passwords stored in AWS Secrets Manager.
+//!
+//! [`SecretsManagerSource`] implements [`DatabaseCredentials`] for databases
+//! without IAM authentication, so applications keep one code path whether
+//! they connect with tokens or with a rotated password:
+//!
+//! ```rust,no_run
+//! # use aws_rds_signer::{DatabaseCredentials, SecretsManagerSource, Signer};
+//! # async fn connect(iam: bool) -> Result<(), aws_rds_signer::Error> {
+//! let credentials: Box<dyn DatabaseCredentials> = if iam {
+//!     Box::new(Signer::from_env()?)
+//! } else {
+//!     Box::new(SecretsManagerSource::new("arn:aws:secretsmanager:us-east-1:123456789012:secret:prod-db").await)
+//! };
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::{PoisonError, RwLock};
+use std::time::{Duration, SystemTime};
+
+use aws_config::SdkConfig;
+use aws_sdk_secretsmanager::Client;
+use aws_smithy_json::deserialize::{json_token_iter, Token};
+use aws_smithy_types::error::display::DisplayErrorContext;
+
+use crate::database_credentials::{DatabaseCredentials, Password, PasswordFuture};
+
+/// How long a fetched password is used before the secret is read again, by default.
+const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// A password source that reads a secret from AWS Secrets Manager.
+///
+/// The secret is read on the first call to [`DatabaseCredentials::password`]
+/// and again once the refresh interval has passed, so rotated passwords are
+/// picked up. Secrets in the JSON format RDS uses for managed and rotated
+/// credentials, e.g. `{"username": "app", "password": "..."}`, provide their
+/// `password` field; any other secret string is used as the password as is.
+#[derive(Debug)]
+pub struct SecretsManagerSource {
+    /// The Secrets Manager client.
+    client: Client,
+    /// The ARN or name of the secret.
+    secret_id: String,
+    /// How long a fetched password is used before the secret is read again.
+    refresh_interval: Duration,
+    /// The last fetched password and when it was fetched.
+    cached: RwLock<Option<(Password, SystemTime)>>,
+}
+
+impl SecretsManagerSource {
+    /// Creates a source for the secret `secret_id`, using the default AWS configuration.
+    ///
+    /// Equivalent to [`Self::with_config`] with the configuration loaded by
+    /// `aws_config::load_defaults`.
+    ///
+    /// # Arguments
+    /// * `secret_id` - The ARN or name of the secret
+    pub async fn new(secret_id: impl Into<String>) -> Self {
+        let sdk_config = aws_config::load_defaults(crate::sign::behavior_version()).await;
+        Self::with_config(&sdk_config, secret_id)
+    }
+
+    /// Creates a source for the secret `secret_id`, using `sdk_config` for the Secrets Manager API.
+    ///
+    /// # Arguments
+    /// * `sdk_config` - The AWS configuration used for the Secrets Manager API
+    /// * `secret_id` - The ARN or name of the secret
+    #[must_use]
+    pub fn with_config(sdk_config: &SdkConfig, secret_id: impl Into<String>) -> Self {
+        Self {
+            client: Client::new(sdk_config),
+            secret_id: secret_id.into(),
+            refresh_interval: DEFAULT_REFRESH_INTERVAL,
+            cached: RwLock::default(),
+        }
+    }
+
+    /// Sets how long a fetched password is used before the secret is read again.
+    ///
+    /// Defaults to one hour. Shorten it if the secret rotates more often, since
+    /// connections opened with the old password fail after a rotation until
+    /// the secret is read again.
+    #[must_use]
+    pub const fn refresh_interval(mut self, refresh_interval: Duration) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    /// Returns the cached password, or reads the secret if it is stale.
+    async fn cached_password(&self) -> Result<Password, crate::Error> {
+        let cached = self
+            .cached
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let now = SystemTime::now();
+        if let Some((password, fetched_at)) = cached {
+            // An interval too large to add, e.g. `Duration::MAX`, never expires.
+            if fetched_at
+                .checked_add(self.refresh_interval)
+                .is_none_or(|deadline| now < deadline)
+            {
+                return Ok(password);
+            }
+        }
+        let password = self.fetch_password().await?;
+        *self.cached.write().unwrap_or_else(PoisonError::into_inner) =
+            Some((password.clone(), now));
+        Ok(password)
+    }
+
+    /// Reads the secret with `GetSecretValue` and extracts the password.
+    async fn fetch_password(&self) -> Result<Password, crate::Error> {
+        let secret_id = &self.secret_id;
+        let output = self
+            .client
+            .get_secret_value()
+            .secret_id(secret_id)
+            .send()
+            .await
+            .map_err(|e| {
+                crate::Error::SecretsManagerError(format!(
+                    "GetSecretValue for `{secret_id}` failed: {}",
+                    DisplayErrorContext(e)
+                ))
+            })?;
+        let secret = output.secret_string().ok_or_else(|| {
+            crate::Error::SecretsManagerError(format!("secret `{secret_id}` has no string value"))
+        })?;
+        secret_password(secret)
+            .map(Password::new)
+            .map_err(|reason| {
+                crate::Error::SecretsManagerError(format!("secret `{secret_id}` {reason}"))
+            })
+    }
+}
+
+impl DatabaseCredentials for SecretsManagerSource {
+    fn password(&self) -> PasswordFuture<'_> {
+        Box::pin(self.cached_password())
+    }
+}
+
+/// Returns the password in a secret string: the `password` field of a JSON object, or the whole string.
+fn secret_password(secret: &str) -> Result<String, &'static str> {
+    if !secret.trim_start().starts_with('{') {
+        return Ok(secret.to_string());
+    }
+    let invalid = |_| "is not valid JSON";
+    let mut tokens = json_token_iter(secret.as_bytes());
+    let mut depth = 0usize;
+    while let Some(token) = tokens.next() {
+        match token.map_err(invalid)? {
+            Token::StartObject { .. } | Token::StartArray { .. } => depth += 1,
+            Token::EndObject { .. } | Token::EndArray { .. } => depth -= 1,
+            Token::ObjectKey { key, .. } if depth == 1 && key.as_escaped_str() == "password" => {
+                return match tokens.next().transpose().map_err(invalid)? {
+                    Some(Token::ValueString { value, .. }) => value
+                        .to_unescaped()
+                        .map(std::borrow::Cow::into_owned)
+                        .map_err(|_| "is not valid JSON"),
+                    _ => Err("has a `password` field that is not a string"),
+                };
+            }
+            _ => {}
+        }
+    }
+    Err("has no `password` field")
+}
@@ -3,20 +3,449 @@
 //! This module provides the core functionality for generating authentication tokens
 //! that can be used to connect to AWS RDS instances using IAM authentication.
 
+use std::hash::{Hash, Hasher};
+use std::net::Ipv6Addr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, PoisonError, RwLock};
 use std::time::Duration;
 use std::time::SystemTime;
 
-use aws_config::BehaviorVersion;
-use aws_credential_types::provider::ProvideCredentials;
+use aws_config::identity::IdentityCache;
+use aws_config::{BehaviorVersion, SdkConfig};
+use aws_credential_types::provider::error::CredentialsError;
+use aws_credential_types::provider::{ProvideCredentials, SharedCredentialsProvider};
 use aws_credential_types::Credentials;
 use aws_sigv4::http_request::{sign, SignableBody, SignableRequest, SigningSettings};
 use aws_sigv4::sign::v4;
+use aws_smithy_json::serialize::JsonObjectWriter;
+use aws_smithy_runtime_api::client::http::{HttpClient, SharedHttpClient};
+use aws_smithy_runtime_api::client::identity::Identity;
+use aws_smithy_types::date_time::Format;
+use aws_smithy_types::retry::RetryConfig;
+use aws_smithy_types::{DateTime, Number};
+use aws_types::os_shim_internal::{Env, Fs};
+use aws_types::region::Region;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use crate::container::ContainerEndpoint;
+use crate::credential_process::CredentialProcess;
+use crate::credential_source::{CredentialSource, CredentialSourceProvider};
+use crate::credentials::{self, AssumeRole, EndpointVariants, StsEndpoint, WebIdentity};
+use crate::database_url::{self, DatabaseUrl, Spec};
+use crate::engine::Engine;
+use crate::refresh_schedule;
+use crate::signer_config::SignerConfig;
+use crate::validation::{self, Field, ValidationError, MAX_EXPIRES_IN};
+
+/// The `SigV4` service name of RDS IAM database authentication.
+pub(crate) const RDS_DB_SERVICE: &str = "rds-db";
+
+/// The lifetime of tokens unless configured otherwise.
+pub(crate) const DEFAULT_EXPIRES_IN: Duration = Duration::from_secs(900);
+
+/// The longest `X-Amz-Expires` a `SigV4` presigned request accepts, in seconds.
+const MAX_PRESIGNED_EXPIRES: u64 = 604_800;
+
+/// Characters percent-encoded in query parameters: all but the `SigV4` unreserved set.
+const QUERY_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// The longest credential cache TTL passed to the SDK identity cache.
+///
+/// The identity cache adds its default expiration to the current time, so
+/// longer TTLs, which never expire in practice, are capped to avoid overflow.
+const MAX_IDENTITY_CACHE_TTL: Duration = Duration::from_secs(100 * 365 * 24 * 60 * 60);
+
+/// Returns the AWS SDK behavior version used to load the default configuration.
+///
+/// It stays pinned so that upgrading the SDK does not change the defaults,
+/// such as retries, timeouts, and proxy handling, that credential resolution
+/// runs with.
+#[allow(deprecated)]
+pub(crate) fn behavior_version() -> BehaviorVersion {
+    BehaviorVersion::v2025_01_17()
+}
+
+/// A value accepted as the AWS region of a signer.
+///
+/// Implemented for region codes such as `"us-east-1"` and for
+/// [`Region`], so regions from an `SdkConfig` or a region provider can be
+/// passed as they are.
+pub trait IntoRegion {
+    /// Converts the value into a [`Region`].
+    fn into_region(self) -> Region;
+}
+
+impl IntoRegion for Region {
+    fn into_region(self) -> Region {
+        self
+    }
+}
+
+impl IntoRegion for &Region {
+    fn into_region(self) -> Region {
+        self.clone()
+    }
+}
+
+impl IntoRegion for String {
+    fn into_region(self) -> Region {
+        Region::new(self)
+    }
+}
+
+impl IntoRegion for &String {
+    fn into_region(self) -> Region {
+        Region::new(self.clone())
+    }
+}
+
+impl IntoRegion for &str {
+    fn into_region(self) -> Region {
+        Region::new(self.to_string())
+    }
+}
+
+/// How the signer handles credentials that expire before the token would.
+///
+/// A token signed with session credentials stops working once they expire,
+/// even if its own lifetime has not elapsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ExpiryBehavior {
+    /// Signs the token with the configured lifetime and logs a warning.
+    #[default]
+    Warn,
+    /// Shortens the token lifetime to end when the credentials expire.
+    Clamp,
+    /// Returns a `CredentialExpiryError` instead of signing the token.
+    Error,
+}
+
+/// How the signer handles a token lifetime above the RDS maximum of 15 minutes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ExpiresInLimit {
+    /// Shortens the token lifetime to 15 minutes and logs a warning.
+    #[default]
+    Clamp,
+    /// Returns a `ValidationError` for the `expires_in` field instead of signing the token.
+    Error,
+}
+
+/// An RDS authentication token together with its expiry, region, and user.
+///
+/// `Display` writes the raw token, so it can be passed wherever the database
+/// password is expected. `Debug` shows only its length and expiry, so tokens
+/// do not leak into logs. With the `zeroize` feature, the token is wiped from
+/// memory when dropped.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AuthToken {
+    /// The authentication token, used as the database password.
+    value: String,
+    /// The time at which the token was signed.
+    signed_at: SystemTime,
+    /// The time at which the token stops being accepted.
+    expires_at: SystemTime,
+    /// The expiry of the credentials that signed the token, if they are temporary.
+    credentials_expire_at: Option<SystemTime>,
+    /// The region the token was signed for.
+    region: String,
+    /// The host to connect to with the token.
+    host: String,
+    /// The database port.
+    port: u16,
+    /// The database user the token authenticates.
+    user: String,
+}
+
+/// The former name of [`AuthToken`].
+#[deprecated(note = "renamed to `AuthToken`")]
+pub type Token = AuthToken;
+
+impl AuthToken {
+    /// Parses a token generated elsewhere, reading its lifetime and signing metadata.
+    ///
+    /// The host, port, and user come from the token's address and `DBUser`
+    /// parameter, the signing time from `X-Amz-Date`, the lifetime from
+    /// `X-Amz-Expires`, and the region from the `X-Amz-Credential` scope. The
+    /// signature is not verified (see [`crate::verify`]), and the credentials'
+    /// expiry is unknown.
+    ///
+    /// # Arguments
+    /// * `token` - The token (e.g., `mydb.xxxx.us-east-1.rds.amazonaws.com:5432/?Action=connect&DBUser=app&X-Amz-...`)
+    ///
+    /// # Errors
+    /// * `ParseError` - If the token is malformed or a signing parameter is missing or invalid
+    pub fn parse(token: &str) -> Result<Self, super::Error> {
+        let error = |reason: &str| super::Error::ParseError(format!("invalid RDS token: {reason}"));
+        let url =
+            url::Url::parse(&format!("https://{token}")).map_err(|e| error(&e.to_string()))?;
+        let host = match url.host() {
+            Some(url::Host::Ipv6(address)) => address.to_string(),
+            Some(host) => host.to_string(),
+            None => return Err(error("missing host")),
+        };
+        let params: std::collections::HashMap<_, _> = url.query_pairs().collect();
+        let param = |name: &str| {
+            params
+                .get(name)
+                .ok_or_else(|| error(&format!("missing `{name}` parameter")))
+        };
+        let user = param("DBUser")?.to_string();
+        let signed_at = param("X-Amz-Date")?;
+        // `X-Amz-Date` is a basic-format timestamp such as `20240101T001500Z`.
+        if !(signed_at.len() == 16
+            && signed_at.is_ascii()
+            && signed_at.as_bytes()[8] == b'T'
+            && signed_at.ends_with('Z'))
+        {
+            return Err(error("`X-Amz-Date` is not a timestamp"));
+        }
+        let signed_at = format!(
+            "{}-{}-{}T{}:{}:{}Z",
+            &signed_at[..4],
+            &signed_at[4..6],
+            &signed_at[6..8],
+            &signed_at[9..11],
+            &signed_at[11..13],
+            &signed_at[13..15]
+        );
+        let signed_at = DateTime::from_str(&signed_at, Format::DateTime)
+            .ok()
+            .and_then(|date_time| SystemTime::try_from(date_time).ok())
+            .ok_or_else(|| error("`X-Amz-Date` is not a timestamp"))?;
+        let expires_in = param("X-Amz-Expires")?
+            .parse::<u64>()
+            .map_err(|_| error("`X-Amz-Expires` is not a number of seconds"))?;
+        let expires_at = Some(expires_in)
+            .filter(|expires_in| *expires_in <= MAX_PRESIGNED_EXPIRES)
+            .and_then(|expires_in| signed_at.checked_add(Duration::from_secs(expires_in)))
+            .ok_or_else(|| error("`X-Amz-Expires` is out of range"))?;
+        let region = param("X-Amz-Credential")?
+            .split('/')
+            .nth(2)
+            .filter(|region| !region.is_empty())
+            .ok_or_else(|| error("`X-Amz-Credential` has no region"))?
+            .to_string();
+        Ok(Self {
+            value: token.to_string(),
+            signed_at,
+            expires_at,
+            credentials_expire_at: None,
+            region,
+            port: url.port_or_known_default().unwrap_or(443),
+            host,
+            user,
+        })
+    }
+
+    /// Returns the authentication token.
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the decoded query parameters of the token, in order.
+    ///
+    /// These are `Action` and `DBUser`, any extra parameters, and the `X-Amz-*`
+    /// signing parameters, for custom drivers and debugging tools that inspect
+    /// or reassemble the token.
+    #[must_use]
+    pub fn params(&self) -> Vec<(String, String)> {
+        self.value
+            .split_once('?')
+            .map(|(_, query)| {
+                url::form_urlencoded::parse(query.as_bytes())
+                    .into_owned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the time at which the token was signed.
+    #[must_use]
+    pub const fn signed_at(&self) -> SystemTime {
+        self.signed_at
+    }
+
+    /// Returns the time at which the token expires.
+    #[must_use]
+    pub const fn expires_at(&self) -> SystemTime {
+        self.expires_at
+    }
+
+    /// Returns whether the token has expired.
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        SystemTime::now() >= self.expires_at
+    }
+
+    /// Returns how long the token remains valid, or zero if it has expired.
+    #[must_use]
+    pub fn ttl_remaining(&self) -> Duration {
+        self.expires_at
+            .duration_since(SystemTime::now())
+            .unwrap_or_default()
+    }
+
+    /// Returns the time after which a new token should be generated.
+    ///
+    /// This is `margin` before the token expires, leaving time to connect
+    /// with a fresh token before the current one is rejected.
+    ///
+    /// # Arguments
+    /// * `margin` - How long before expiry to refresh (e.g., one minute)
+    #[must_use]
+    pub fn refresh_after(&self, margin: Duration) -> SystemTime {
+        self.expires_at
+            .checked_sub(margin)
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+    }
+
+    /// Returns the expiry of the credentials that signed the token.
+    ///
+    /// `None` for long-term credentials, which do not expire.
+    #[must_use]
+    pub const fn credentials_expire_at(&self) -> Option<SystemTime> {
+        self.credentials_expire_at
+    }
+
+    /// Returns the region the token was signed for.
+    #[must_use]
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+
+    /// Returns the host to connect to with the token: the connect host if set, otherwise the signed host.
+    #[must_use]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns the database port.
+    #[must_use]
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns the database user the token authenticates.
+    #[must_use]
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Returns the token and its connection settings as a JSON object.
+    ///
+    /// The object has `token`, `expires_at` (an RFC 3339 timestamp), `host`,
+    /// `port`, and `user` fields, for handing the token to sidecars, shell
+    /// scripts, and services written in other languages.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        let mut object = JsonObjectWriter::new(&mut json);
+        object.key("token").string(&self.value);
+        object.key("expires_at").string(&self.expires_at_rfc3339());
+        object.key("host").string(&self.host);
+        object.key("port").number(Number::PosInt(self.port.into()));
+        object.key("user").string(&self.user);
+        object.finish();
+        json
+    }
+
+    /// Returns the expiry as an RFC 3339 timestamp such as `2024-01-01T00:15:00Z`.
+    pub(crate) fn expires_at_rfc3339(&self) -> String {
+        // Formatting fails only for years outside 0001-9999.
+        DateTime::from(self.expires_at)
+            .fmt(Format::DateTime)
+            .unwrap_or_default()
+    }
+
+    /// Consumes the token and returns its value.
+    #[must_use]
+    pub fn into_value(mut self) -> String {
+        std::mem::take(&mut self.value)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for AuthToken {
+    /// Serializes the same fields as [`AuthToken::to_json`].
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut token = serializer.serialize_struct("AuthToken", 5)?;
+        token.serialize_field("token", &self.value)?;
+        token.serialize_field("expires_at", &self.expires_at_rfc3339())?;
+        token.serialize_field("host", &self.host)?;
+        token.serialize_field("port", &self.port)?;
+        token.serialize_field("user", &self.user)?;
+        token.end()
+    }
+}
+
+#[cfg(feature = "secrecy")]
+impl AuthToken {
+    /// Consumes the token and returns its value as a `SecretString`.
+    #[must_use]
+    pub fn into_secret(self) -> secrecy::SecretString {
+        self.into_value().into()
+    }
+}
+
+#[cfg(feature = "secrecy")]
+impl secrecy::ExposeSecret<str> for AuthToken {
+    fn expose_secret(&self) -> &str {
+        &self.value
+    }
+}
+
+#[cfg(feature = "secrecy")]
+impl From<AuthToken> for secrecy::SecretString {
+    fn from(token: AuthToken) -> Self {
+        token.into_secret()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for AuthToken {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.value);
+    }
+}
+
+impl std::fmt::Debug for AuthToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthToken")
+            .field(
+                "value",
+                &format_args!("<redacted, {} bytes>", self.value.len()),
+            )
+            .field("expires_at", &self.expires_at)
+            .finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Display for AuthToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.value)
+    }
+}
 
 /// A configured signer for generating RDS IAM authentication tokens.
 ///
 /// The signer contains all the necessary configuration to generate authentication
 /// tokens for connecting to an RDS instance. It can be created using the builder
 /// pattern via [`SignerBuilder`].
+///
+/// Clones share the credentials cache. Equality and hashing cover the
+/// configuration only: the cache is ignored, and SDK configurations,
+/// identities, credentials providers, and HTTP clients are compared by
+/// whether they are set, since they cannot be compared by value.
+// The flags are independent builder options rather than states.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug)]
 pub struct Signer {
     /// The duration for which the generated token will be valid.
@@ -25,26 +454,220 @@ pub struct Signer {
     /// The hostname of the RDS instance to connect to.
     /// This should be the endpoint provided by AWS.
     host: String,
+    /// The host clients dial, if it differs from the signed host (e.g., a tunnel or proxy).
+    connect_host: Option<String>,
     /// The port number the database is listening on.
     /// Common values are `5432` for `PostgreSQL` and `3306` for `MySQL`.
     port: u16,
+    /// The database engine, if known.
+    engine: Option<Engine>,
     /// The database user to authenticate as.
     /// This user must be configured in RDS with IAM authentication enabled.
     user: String,
     /// The AWS region where the RDS instance is located.
     /// If not provided, will attempt to use the region from AWS configuration.
-    region: Option<String>,
+    region: Option<Region>,
+    /// The `SigV4` service name tokens are signed for.
+    service_name: String,
+    /// Additional query parameters included in the signed request.
+    extra_params: Vec<(String, String)>,
+    /// A pre-loaded AWS SDK configuration used to resolve credentials and region.
+    /// If not provided, the default configuration is loaded on every token request.
+    sdk_config: Option<SdkConfig>,
+    /// A resolved identity used as the signing credentials instead of any provider.
+    identity: Option<Identity>,
+    /// A custom credentials provider used instead of the one from the AWS configuration.
+    credentials_provider: Option<SharedCredentialsProvider>,
+    /// A web identity token file exchanged for role credentials (e.g., EKS IRSA).
+    web_identity: Option<WebIdentity>,
+    /// A container credentials endpoint (full URI or path relative to the ECS endpoint).
+    container_credentials_uri: Option<String>,
+    /// The file holding the authorization token for the container credentials endpoint.
+    container_authorization_token_file: Option<PathBuf>,
+    /// A `credential_process` command that prints credentials as JSON.
+    credential_process: Option<String>,
+    /// The time after which a credential process is killed.
+    credential_process_timeout: Option<Duration>,
+    /// A named profile from the AWS config files used for credentials and region.
+    profile: Option<String>,
+    /// Whether the selected profile is SSO-backed, enabling SSO session error reporting.
+    sso: bool,
+    /// The maximum time allowed for resolving credentials and region.
+    credential_timeout: Option<Duration>,
+    /// Retries with backoff for transient credential provider failures.
+    credential_retry_config: Option<RetryConfig>,
+    /// Whether the EC2 instance metadata service is skipped when resolving credentials and region.
+    disable_imds: bool,
+    /// Whether the default chain skips IMDS and reports why each provider failed.
+    fail_fast: bool,
+    /// Whether a missing region is an error rather than defaulting to `us-east-1`.
+    strict_region: bool,
+    /// How credentials expiring before the token are handled.
+    expiry_behavior: ExpiryBehavior,
+    /// How a token lifetime above the RDS maximum is handled.
+    expires_in_limit: ExpiresInLimit,
+    /// The STS endpoint used for assuming roles and exchanging web identity tokens.
+    sts_endpoint: Option<StsEndpoint>,
+    /// Whether FIPS and dual-stack endpoints are used for credential requests.
+    endpoint_variants: EndpointVariants,
+    /// The HTTP client used for credential resolution (e.g., with proxy or custom CA settings).
+    http_client: Option<SharedHttpClient>,
+    /// IAM roles assumed in order, starting from the base credentials, before signing.
+    assume_roles: Vec<AssumeRole>,
+    /// How long before their expiry cached credentials are resolved again.
+    credential_cache_buffer_time: Option<Duration>,
+    /// How long credentials without an expiry are cached.
+    credential_cache_ttl: Option<Duration>,
+    /// Credentials and region resolved by a previous token request, reused until the credentials expire.
+    /// Shared with signers derived by [`Signer::with_user`].
+    credentials_cache: Arc<CredentialsCache>,
+}
+
+/// The credentials cache shared by a signer and its clones.
+#[derive(Debug, Default)]
+struct CredentialsCache {
+    /// The most recently resolved credentials and region.
+    resolved: RwLock<Option<Resolved>>,
+    /// Held while credentials are being resolved, so concurrent requests wait
+    /// for that resolution instead of starting their own.
+    resolving: tokio::sync::Mutex<()>,
+}
+
+/// Credentials resolved for signing, together with the signing region.
+#[derive(Debug, Clone)]
+struct Resolved {
+    /// The resolved AWS credentials.
+    credentials: Credentials,
+    /// The region the token is signed for.
+    region: String,
+    /// The time after which the credentials are resolved again, if any.
+    refresh_at: Option<SystemTime>,
+}
+
+impl Resolved {
+    /// Returns whether the credentials are still valid at `now`.
+    fn is_valid_at(&self, now: SystemTime) -> bool {
+        self.refresh_at.is_none_or(|refresh_at| refresh_at > now)
+    }
 }
 
 impl Default for Signer {
     fn default() -> Self {
         Self {
-            expires_in: Duration::from_secs(900),
+            expires_in: DEFAULT_EXPIRES_IN,
             host: "localhost".to_string(),
+            connect_host: None,
             port: 5432,
+            engine: None,
             user: "postgres".to_string(),
             region: None,
+            service_name: RDS_DB_SERVICE.to_string(),
+            extra_params: Vec::new(),
+            sdk_config: None,
+            identity: None,
+            credentials_provider: None,
+            web_identity: None,
+            container_credentials_uri: None,
+            container_authorization_token_file: None,
+            credential_process: None,
+            credential_process_timeout: None,
+            profile: None,
+            sso: false,
+            credential_timeout: None,
+            credential_retry_config: None,
+            disable_imds: false,
+            fail_fast: false,
+            strict_region: false,
+            expiry_behavior: ExpiryBehavior::Warn,
+            expires_in_limit: ExpiresInLimit::Clamp,
+            sts_endpoint: None,
+            endpoint_variants: EndpointVariants::default(),
+            http_client: None,
+            assume_roles: Vec::new(),
+            credential_cache_buffer_time: None,
+            credential_cache_ttl: None,
+            credentials_cache: Arc::default(),
+        }
+    }
+}
+
+impl Clone for Signer {
+    fn clone(&self) -> Self {
+        self.with_cache(Arc::clone(&self.credentials_cache))
+    }
+}
+
+impl PartialEq for Signer {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Signer {}
+
+impl Hash for Signer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+/// Wipes the signature and the signed query parameters once the token holds them.
+#[cfg(feature = "zeroize")]
+fn zeroize_signing_output(
+    mut signature: String,
+    params: Vec<(&'static str, std::borrow::Cow<'static, str>)>,
+) {
+    use zeroize::Zeroize;
+
+    signature.zeroize();
+    for (_, value) in params {
+        if let std::borrow::Cow::Owned(mut value) = value {
+            value.zeroize();
+        }
+    }
+}
+
+/// Returns the value of the `{prefix}_{name}` environment variable, if set.
+fn env_var(env: &Env, prefix: &str, name: &str) -> Result<Option<String>, super::Error> {
+    let key = format!("{prefix}_{name}");
+    match env.get(&key) {
+        Ok(value) => Ok(Some(value)),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(e) => Err(super::Error::EnvVarError(format!("{key}: {e}"))),
+    }
+}
+
+/// Parses a compact spec such as `iam_user@mydb.xxxx.us-east-1.rds.amazonaws.com:5432?region=us-east-1&expires=600`.
+///
+/// The user and host are required. The port defaults to 5432, and the
+/// optional `region` and `expires` (in seconds) query parameters set the
+/// signing region and token lifetime. The result is validated with
+/// [`SignerBuilder::try_build`].
+impl FromStr for Signer {
+    type Err = super::Error;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let spec = Spec::parse(spec)?;
+        let mut builder = Self::builder().host(spec.host).user(spec.user);
+        if let Some(port) = spec.port {
+            builder = builder.port(port);
         }
+        if let Some(region) = spec.region {
+            builder = builder.region(region);
+        }
+        if let Some(expires_in) = spec.expires_in {
+            builder = builder.expires_in(expires_in);
+        }
+        builder.try_build()
+    }
+}
+
+impl TryFrom<&str> for Signer {
+    type Error = super::Error;
+
+    /// Parses a compact spec as described for the [`FromStr`] implementation.
+    fn try_from(spec: &str) -> Result<Self, Self::Error> {
+        spec.parse()
     }
 }
 
@@ -55,6 +678,10 @@ impl Default for Signer {
 #[derive(Debug)]
 pub struct SignerBuilder {
     signer: Signer,
+    /// Whether the host was set explicitly rather than left at its default.
+    host_set: bool,
+    /// Whether the port was set explicitly rather than derived from the engine.
+    port_set: bool,
 }
 
 impl SignerBuilder {
@@ -63,11 +690,16 @@ impl SignerBuilder {
     fn new() -> Self {
         Self {
             signer: Signer::default(),
+            host_set: false,
+            port_set: false,
         }
     }
 
     /// Sets the token expiration duration.
     ///
+    /// RDS accepts tokens for at most 15 minutes; longer lifetimes are handled
+    /// as configured with [`Self::expires_in_limit`].
+    ///
     /// # Arguments
     /// * `expires_in` - The duration for which the token will be valid
     #[must_use]
@@ -76,124 +708,1585 @@ impl SignerBuilder {
         self
     }
 
-    /// Sets the RDS instance hostname.
+    /// Sets the token expiration in whole seconds.
     ///
     /// # Arguments
-    /// * `host` - The RDS endpoint (e.g., "mydb.123456789012.us-east-1.rds.amazonaws.com")
+    /// * `seconds` - The number of seconds the token will be valid (at most 900)
     #[must_use]
-    pub fn host(mut self, host: impl Into<String>) -> Self {
-        self.signer.host = host.into();
+    pub const fn expires_in_secs(mut self, seconds: u64) -> Self {
+        self.signer.expires_in = Duration::from_secs(seconds);
         self
     }
 
-    /// Sets the database port number.
+    /// Sets how a token lifetime above the RDS maximum of 15 minutes is handled.
+    ///
+    /// Defaults to [`ExpiresInLimit::Clamp`]. [`Self::try_build`] rejects such
+    /// lifetimes regardless of this setting.
     ///
     /// # Arguments
-    /// * `port` - The port number (e.g., 5432 for `PostgreSQL`)
+    /// * `limit` - Whether to clamp the lifetime or fail
     #[must_use]
-    pub fn port(mut self, port: impl Into<u16>) -> Self {
-        self.signer.port = port.into();
+    pub const fn expires_in_limit(mut self, limit: ExpiresInLimit) -> Self {
+        self.signer.expires_in_limit = limit;
         self
     }
 
-    /// Sets the AWS region.
+    /// Sets the RDS instance hostname.
+    ///
+    /// IPv6 addresses may be given with or without brackets; they are
+    /// bracketed in the signed URL either way. A port embedded in the host,
+    /// as in `mydb.xxxx.us-east-1.rds.amazonaws.com:5432`, is split off and
+    /// set as with [`Self::port`].
     ///
     /// # Arguments
-    /// * `region` - The AWS region (e.g., "us-east-1")
+    /// * `host` - The RDS endpoint (e.g., "mydb.123456789012.us-east-1.rds.amazonaws.com")
     #[must_use]
-    pub fn region(mut self, region: impl Into<String>) -> Self {
-        self.signer.region = Some(region.into());
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        let host = host.into();
+        self.host_set = true;
+        if let Some((host, port)) = database_url::split_port(&host) {
+            self.signer.host = host;
+            return self.port(port);
+        }
+        self.signer.host = validation::unbracket_host(host);
         self
     }
 
-    /// Sets the database username.
+    /// Sets the RDS endpoint the token is signed for, the same as [`Self::host`].
+    ///
+    /// Use it together with [`Self::connect_host`] when clients reach the
+    /// database through an SSH tunnel, a proxy such as `PgBouncer`, or a CNAME.
     ///
     /// # Arguments
-    /// * `user` - The database user to authenticate as
-    #[must_use]
-    pub fn user(mut self, user: impl Into<String>) -> Self {
-        self.signer.user = user.into();
-        self
-    }
-
-    /// Builds the final [`Signer`] instance.
+    /// * `host` - The RDS endpoint (e.g., "mydb.123456789012.us-east-1.rds.amazonaws.com")
     #[must_use]
-    pub fn build(self) -> Signer {
-        self.signer
+    pub fn signing_host(self, host: impl Into<String>) -> Self {
+        self.host(host)
     }
-}
 
-impl Signer {
-    /// Creates a new `SignerBuilder` for configuring a Signer instance.
+    /// Sets the host clients dial when it differs from the signed RDS endpoint.
+    ///
+    /// Tokens are always signed for the RDS endpoint set with [`Self::host`];
+    /// connection strings use this host instead.
+    ///
+    /// # Arguments
+    /// * `host` - The host to connect to (e.g., "localhost" for an SSH tunnel)
     #[must_use]
-    pub fn builder() -> SignerBuilder {
-        SignerBuilder::new()
+    pub fn connect_host(mut self, host: impl Into<String>) -> Self {
+        self.signer.connect_host = Some(validation::unbracket_host(host.into()));
+        self
     }
 
-    /// Generates an authentication token for connecting to the RDS instance.
+    /// Sets the host, port, user, and region from a database URL.
     ///
-    /// This method will use the configured AWS credentials to generate a signed
-    /// authentication token that can be used to connect to the RDS instance.
-    /// The token is valid for the duration specified in the configuration.
+    /// The port defaults to that of the scheme (`postgres`/`postgresql` or
+    /// `mysql`/`mariadb`), and the region is inferred from RDS endpoint
+    /// hostnames unless one was already set.
     ///
-    /// # Returns
-    /// * `Ok(String)` - The authentication token
-    /// * `Err(Error)` - If token generation fails
+    /// # Arguments
+    /// * `database_url` - The URL (e.g., `postgres://iam_user@mydb.xxxx.us-east-1.rds.amazonaws.com:5432/app`)
     ///
     /// # Errors
-    /// * `SignerError` - If signing the request fails
-    /// * `ParseError` - If URL parsing fails
-    pub async fn fetch_token(&self) -> Result<String, super::Error> {
-        let config = aws_config::load_defaults(BehaviorVersion::v2025_01_17()).await;
-        let credentials: Credentials = config
-            .credentials_provider()
-            .ok_or_else(|| super::Error::SignerError("no credentials provider found".to_string()))?
-            .provide_credentials()
-            .await
-            .map_err(|e| super::Error::SignerError(e.to_string()))?;
-        let identity = credentials.into();
-        let region = self.region.clone().unwrap_or_else(|| {
-            config
-                .region()
-                .map_or_else(|| "us-east-1".to_string(), ToString::to_string)
-        });
-
-        let mut signing_settings = SigningSettings::default();
-        signing_settings.expires_in = Some(self.expires_in);
-        signing_settings.signature_location =
-            aws_sigv4::http_request::SignatureLocation::QueryParams;
-
-        let signing_params = v4::SigningParams::builder()
-            .identity(&identity)
-            .region(&region)
-            .name("rds-db")
-            .time(SystemTime::now())
-            .settings(signing_settings)
-            .build()
-            .map_err(|e| super::Error::SignerError(e.to_string()))?;
+    /// * `ParseError` - If the URL is malformed, has no host, or uses an unsupported scheme
+    pub fn url(self, database_url: &str) -> Result<Self, super::Error> {
+        Ok(self.database_url(DatabaseUrl::parse(database_url)?))
+    }
 
-        let url = format!(
-            "https://{hostname}:{port}/?Action=connect&DBUser={username}",
-            hostname = self.host,
-            port = self.port,
-            username = self.user
-        );
+    /// Overlays settings from `RDS_SIGNER_*` environment variables.
+    ///
+    /// Equivalent to [`Self::apply_env_with_prefix`] with the `RDS_SIGNER` prefix.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::apply_env_with_prefix`].
+    pub fn apply_env(self) -> Result<Self, super::Error> {
+        self.apply_env_with_prefix("RDS_SIGNER")
+    }
 
-        let signable_request =
-            SignableRequest::new("GET", &url, std::iter::empty(), SignableBody::Bytes(&[]))
-                .map_err(|e| super::Error::SignerError(e.to_string()))?;
+    /// Overlays settings from environment variables with the given prefix.
+    ///
+    /// Each of `{prefix}_HOST`, `{prefix}_PORT`, `{prefix}_USER`,
+    /// `{prefix}_REGION`, and `{prefix}_EXPIRES_IN` (in seconds) that is set
+    /// replaces the value configured so far, so programmatic defaults can be
+    /// overridden per deployment.
+    ///
+    /// # Arguments
+    /// * `prefix` - The variable name prefix (e.g., "DB" for `DB_HOST`)
+    ///
+    /// # Errors
+    /// * `EnvVarError` - If a value is not valid Unicode or cannot be parsed
+    pub fn apply_env_with_prefix(self, prefix: &str) -> Result<Self, super::Error> {
+        self.overlay_env(prefix, &Env::real())
+    }
 
-        let (signing_instructions, _signature) = sign(signable_request, &signing_params.into())
-            .map_err(|e| super::Error::SignerError(e.to_string()))?
-            .into_parts();
+    /// Applies the settings found in `{prefix}_*` environment variables, requiring a host and user.
+    pub(crate) fn env(self, prefix: &str, env: &Env) -> Result<Self, super::Error> {
+        for name in ["HOST", "USER"] {
+            if env_var(env, prefix, name)?.is_none() {
+                return Err(super::Error::EnvVarError(format!(
+                    "{prefix}_{name} is not set"
+                )));
+            }
+        }
+        self.overlay_env(prefix, env)
+    }
 
-        let mut url = url::Url::parse(&url).map_err(|e| super::Error::ParseError(e.to_string()))?;
-        for (name, value) in signing_instructions.params() {
-            url.query_pairs_mut().append_pair(name, value);
+    /// Applies the settings found in those `{prefix}_*` environment variables that are set.
+    pub(crate) fn overlay_env(mut self, prefix: &str, env: &Env) -> Result<Self, super::Error> {
+        let var = |name: &str| env_var(env, prefix, name);
+        let parse = |name: &str, value: &str| {
+            super::Error::EnvVarError(format!("{prefix}_{name}: `{value}` is not a valid number"))
+        };
+        if let Some(host) = var("HOST")? {
+            self = self.host(host);
+        }
+        if let Some(port) = var("PORT")? {
+            self = self.port(port.parse::<u16>().map_err(|_| parse("PORT", &port))?);
+        }
+        if let Some(user) = var("USER")? {
+            self = self.user(user);
+        }
+        if let Some(region) = var("REGION")? {
+            self = self.region(region);
         }
+        if let Some(expires_in) = var("EXPIRES_IN")? {
+            let seconds = expires_in
+                .parse::<u64>()
+                .map_err(|_| parse("EXPIRES_IN", &expires_in))?;
+            self = self.expires_in(Duration::from_secs(seconds));
+        }
+        Ok(self)
+    }
 
-        let response = url.to_string().split_off("https://".len());
+    /// Applies the settings of a parsed database URL.
+    fn database_url(mut self, database_url: DatabaseUrl) -> Self {
+        self = self
+            .host(database_url.host)
+            .engine(database_url.engine)
+            .port(database_url.port);
+        if let Some(user) = database_url.user {
+            self = self.user(user);
+        }
+        if self.signer.region.is_none() {
+            self.signer.region = database_url.region.map(Region::new);
+        }
+        self
+    }
 
-        Ok(response)
+    /// Sets the hostname and port from a combined `host:port` string.
+    ///
+    /// IPv6 addresses must be enclosed in brackets (e.g., `[fd00::1]:5432`).
+    ///
+    /// # Arguments
+    /// * `host_and_port` - The address (e.g., "mydb.xxxx.us-east-1.rds.amazonaws.com:5432")
+    ///
+    /// # Errors
+    /// * `ParseError` - If the host or port is missing or malformed
+    pub fn host_and_port(self, host_and_port: &str) -> Result<Self, super::Error> {
+        let (host, port) = database_url::parse_host_and_port(host_and_port)?;
+        Ok(self.host(host).port(port))
+    }
+
+    /// Sets the database port number.
+    ///
+    /// # Arguments
+    /// * `port` - The port number (e.g., 5432 for `PostgreSQL`)
+    #[must_use]
+    pub fn port(mut self, port: impl Into<u16>) -> Self {
+        self.signer.port = port.into();
+        self.port_set = true;
+        self
+    }
+
+    /// Sets the database engine.
+    ///
+    /// Unless a port was set explicitly, the port becomes the engine's default
+    /// (5432 for `PostgreSQL`, 3306 for `MySQL` and `MariaDB`).
+    ///
+    /// # Arguments
+    /// * `engine` - The database engine (e.g., `Engine::AuroraPostgres`)
+    #[must_use]
+    pub const fn engine(mut self, engine: Engine) -> Self {
+        self.signer.engine = Some(engine);
+        if !self.port_set {
+            self.signer.port = engine.default_port();
+        }
+        self
+    }
+
+    /// Sets the AWS region.
+    ///
+    /// When unset, the region is taken from RDS endpoint hostnames such as
+    /// `mydb.xxxx.eu-west-1.rds.amazonaws.com`, and otherwise from the AWS
+    /// configuration.
+    ///
+    /// # Arguments
+    /// * `region` - The AWS region (e.g., "us-east-1" or a [`Region`])
+    #[must_use]
+    pub fn region(mut self, region: impl IntoRegion) -> Self {
+        self.signer.region = Some(region.into_region());
+        self
+    }
+
+    /// Sets the `SigV4` service name tokens are signed for.
+    ///
+    /// Defaults to `rds-db`. Override it only for services that use the same
+    /// presigned token scheme under another name.
+    ///
+    /// # Arguments
+    /// * `service_name` - The signing name of the service
+    #[must_use]
+    pub fn service_name(mut self, service_name: impl Into<String>) -> Self {
+        self.signer.service_name = service_name.into();
+        self
+    }
+
+    /// Adds query parameters to the signed request.
+    ///
+    /// The parameters follow `Action` and `DBUser` in the token and are covered
+    /// by the signature, so parameters AWS introduces later can be used before
+    /// this crate supports them. Calling this again appends to the parameters.
+    ///
+    /// # Arguments
+    /// * `params` - The parameter names and values, percent-encoded when signing
+    #[must_use]
+    pub fn extra_params<K: AsRef<str>, V: AsRef<str>>(mut self, params: &[(K, V)]) -> Self {
+        self.signer.extra_params.extend(
+            params
+                .iter()
+                .map(|(name, value)| (name.as_ref().to_string(), value.as_ref().to_string())),
+        );
+        self
+    }
+
+    /// Sets a pre-loaded AWS SDK configuration.
+    ///
+    /// The signer uses this configuration's credentials provider and region
+    /// instead of loading the default configuration on every token request.
+    ///
+    /// # Arguments
+    /// * `sdk_config` - The shared AWS configuration (e.g., from `aws_config::load_defaults`)
+    #[must_use]
+    pub fn sdk_config(mut self, sdk_config: &SdkConfig) -> Self {
+        self.signer.sdk_config = Some(sdk_config.clone());
+        self
+    }
+
+    /// Sets a custom credentials provider.
+    ///
+    /// The provider takes precedence over the credentials provider of the AWS
+    /// configuration, which is still used to resolve the region.
+    ///
+    /// # Arguments
+    /// * `provider` - The credentials provider (e.g., a Vault-backed or test provider)
+    #[must_use]
+    pub fn credentials_provider(mut self, provider: impl ProvideCredentials + 'static) -> Self {
+        self.signer.credentials_provider = Some(SharedCredentialsProvider::new(provider));
+        self
+    }
+
+    /// Sets a custom credential source (e.g., an OS keyring or HSM-backed store).
+    ///
+    /// A simpler alternative to [`Self::credentials_provider`] that does not
+    /// require implementing `ProvideCredentials`; it takes the same precedence.
+    ///
+    /// # Arguments
+    /// * `source` - The credential source
+    #[must_use]
+    pub fn credential_source(mut self, source: impl CredentialSource + 'static) -> Self {
+        self.signer.credentials_provider = Some(SharedCredentialsProvider::new(
+            CredentialSourceProvider(Arc::new(source)),
+        ));
+        self
+    }
+
+    /// Sets a shared credentials provider.
+    ///
+    /// The signer uses this exact provider instance, including any caching it
+    /// performs, so it can be shared with the application's other AWS SDK
+    /// clients (e.g., the provider of an `SdkConfig`). It takes the same
+    /// precedence as [`Self::credentials_provider`].
+    ///
+    /// # Arguments
+    /// * `provider` - The shared credentials provider
+    #[must_use]
+    pub fn shared_credentials_provider(mut self, provider: SharedCredentialsProvider) -> Self {
+        self.signer.credentials_provider = Some(provider);
+        self
+    }
+
+    /// Sets an already resolved identity to sign tokens with.
+    ///
+    /// The identity takes precedence over every credentials source, so callers
+    /// sharing an identity resolver with other SDK clients can skip credential
+    /// resolution entirely. Its data must be AWS [`Credentials`]; configured
+    /// roles are still assumed on top of it.
+    ///
+    /// # Arguments
+    /// * `identity` - The identity (e.g., from an `IdentityCache` or `Identity::from(credentials)`)
+    #[must_use]
+    pub fn identity(mut self, identity: Identity) -> Self {
+        self.signer.identity = Some(identity);
+        self
+    }
+
+    /// Configures web identity federation (e.g., EKS IAM roles for service accounts).
+    ///
+    /// The token file is re-read on every credential resolution, so tokens
+    /// rotated by the kubelet are picked up across `fetch_token` calls.
+    ///
+    /// # Arguments
+    /// * `role_arn` - The role to assume with the web identity token
+    /// * `token_file` - The path to the web identity token file
+    #[must_use]
+    pub fn web_identity(
+        mut self,
+        role_arn: impl Into<String>,
+        token_file: impl Into<PathBuf>,
+    ) -> Self {
+        self.signer.web_identity = Some(WebIdentity {
+            role_arn: role_arn.into(),
+            token_file: token_file.into(),
+        });
+        self
+    }
+
+    /// Sets the container credentials endpoint (ECS task roles or compatible services).
+    ///
+    /// A full URI such as `http://localhost:8080/credentials` is used as-is, while
+    /// a path such as `/v2/credentials/...` is resolved against the ECS endpoint,
+    /// mirroring `AWS_CONTAINER_CREDENTIALS_FULL_URI` and `AWS_CONTAINER_CREDENTIALS_RELATIVE_URI`.
+    ///
+    /// # Arguments
+    /// * `uri` - The full credentials URI or a relative path
+    #[must_use]
+    pub fn container_credentials_uri(mut self, uri: impl Into<String>) -> Self {
+        self.signer.container_credentials_uri = Some(uri.into());
+        self
+    }
+
+    /// Configures credentials from the EKS Pod Identity agent.
+    ///
+    /// The endpoint and token file are taken from `AWS_CONTAINER_CREDENTIALS_FULL_URI`
+    /// and `AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE`, which EKS injects into pods
+    /// with a Pod Identity association, falling back to the agent's defaults
+    /// (`http://169.254.170.23/v1/credentials` and the projected service account
+    /// token). The token file is re-read on every credential resolution.
+    #[must_use]
+    pub fn eks_pod_identity(mut self) -> Self {
+        let endpoint = ContainerEndpoint::eks_pod_identity(&Env::real());
+        self.signer.container_credentials_uri = Some(endpoint.uri);
+        self.signer.container_authorization_token_file = endpoint.auth_token_file;
+        self
+    }
+
+    /// Sets the authorization token file for the container credentials endpoint.
+    ///
+    /// The file is re-read on every credential resolution, so rotated tokens are
+    /// picked up mid-process, and its contents are sent as the `Authorization`
+    /// header. It is only used together with [`Self::container_credentials_uri`];
+    /// when unset, `AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE` is used if present.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the authorization token file
+    #[must_use]
+    pub fn container_authorization_token_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.signer.container_authorization_token_file = Some(path.into());
+        self
+    }
+
+    /// Sets a `credential_process` command used to obtain credentials.
+    ///
+    /// The command is executed through the platform shell and must print
+    /// credentials in the version 1 JSON format expected by the AWS CLI.
+    ///
+    /// # Arguments
+    /// * `command` - The command line to execute
+    #[must_use]
+    pub fn credential_process(mut self, command: impl Into<String>) -> Self {
+        self.signer.credential_process = Some(command.into());
+        self
+    }
+
+    /// Sets the maximum time a credential process may run.
+    ///
+    /// When the timeout elapses the process is killed and `fetch_token` fails.
+    /// This also applies to profiles that use `credential_process` directly.
+    ///
+    /// # Arguments
+    /// * `timeout` - The maximum runtime of the process
+    #[must_use]
+    pub fn credential_process_timeout(mut self, timeout: impl Into<Duration>) -> Self {
+        self.signer.credential_process_timeout = Some(timeout.into());
+        self
+    }
+
+    /// Selects a named profile from the AWS config files.
+    ///
+    /// Credentials and region are loaded from this profile rather than the
+    /// default credentials chain.
+    ///
+    /// # Arguments
+    /// * `profile` - The profile name (e.g., "prod-db")
+    #[must_use]
+    pub fn profile(mut self, profile: impl Into<String>) -> Self {
+        self.signer.profile = Some(profile.into());
+        self.signer.sso = false;
+        self
+    }
+
+    /// Selects an SSO (IAM Identity Center) profile from the AWS config files.
+    ///
+    /// Credentials are obtained from the SSO token cache, refreshing tokens that
+    /// are close to expiry. When the session is gone, `fetch_token` returns an
+    /// `SsoSessionError` directing the user to `aws sso login`.
+    ///
+    /// # Arguments
+    /// * `profile` - The name of the SSO-backed profile
+    #[must_use]
+    pub fn sso_profile(mut self, profile: impl Into<String>) -> Self {
+        self.signer.profile = Some(profile.into());
+        self.signer.sso = true;
+        self
+    }
+
+    /// Sets the maximum time allowed for resolving credentials.
+    ///
+    /// The timeout covers the entire provider chain, including configuration
+    /// loading, IMDS, SSO, and STS calls. When it elapses, `fetch_token` returns
+    /// a `TimeoutError` instead of hanging on unreachable endpoints.
+    ///
+    /// # Arguments
+    /// * `timeout` - The maximum credential resolution time
+    #[must_use]
+    pub fn credential_timeout(mut self, timeout: impl Into<Duration>) -> Self {
+        self.signer.credential_timeout = Some(timeout.into());
+        self
+    }
+
+    /// Sets retries for transient failures while resolving credentials.
+    ///
+    /// Provider errors such as unreachable IMDS, STS, or container endpoints are
+    /// retried up to the configured number of attempts with exponential backoff
+    /// between `initial_backoff` and `max_backoff`. Each delay is drawn at
+    /// random up to the backoff, so a fleet of instances that lost IMDS at the
+    /// same time does not retry in lockstep; `with_use_static_exponential_base(true)`
+    /// sleeps for the full backoff instead. Missing or invalid configuration is
+    /// not retried, and signing itself never is, since it cannot fail
+    /// transiently. The [`Self::credential_timeout`] covers all attempts.
+    ///
+    /// # Arguments
+    /// * `retry_config` - The retry settings (e.g., `RetryConfig::standard().with_max_attempts(5)`)
+    #[must_use]
+    pub const fn credential_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.signer.credential_retry_config = Some(retry_config);
+        self
+    }
+
+    /// Skips the EC2 instance metadata service (IMDS) when loading the default configuration.
+    ///
+    /// Outside EC2, for example in containers, the IMDS fallback adds seconds of
+    /// timeouts before credential resolution fails. The metadata client used by
+    /// the default chain only speaks `IMDSv2` (session tokens) and never falls
+    /// back to `IMDSv1`, so disabling it is the only IMDS control needed.
+    ///
+    /// # Arguments
+    /// * `disable_imds` - Whether to skip IMDS
+    #[must_use]
+    pub const fn disable_imds(mut self, disable_imds: bool) -> Self {
+        self.signer.disable_imds = disable_imds;
+        self
+    }
+
+    /// Fails immediately with a detailed error when the default chain finds no credentials.
+    ///
+    /// The EC2 instance metadata service is skipped as with [`Self::disable_imds`],
+    /// and when no provider supplies credentials, `fetch_token` returns a
+    /// `NoCredentialsError` naming each provider that was tried and why it was
+    /// skipped (e.g., an unset environment variable or a missing profile).
+    ///
+    /// # Arguments
+    /// * `fail_fast` - Whether to fail fast
+    #[must_use]
+    pub const fn fail_fast(mut self, fail_fast: bool) -> Self {
+        self.signer.fail_fast = fail_fast;
+        self
+    }
+
+    /// Sets an IAM role to assume before signing tokens.
+    ///
+    /// The role is assumed through STS using the configured credentials, which
+    /// allows connecting to databases in other accounts.
+    ///
+    /// # Arguments
+    /// * `role` - The role ARN or a fully configured [`AssumeRole`]
+    #[must_use]
+    pub fn assume_role(mut self, role: impl Into<AssumeRole>) -> Self {
+        self.signer.assume_roles = vec![role.into()];
+        self
+    }
+
+    /// Sets a chain of IAM roles to assume before signing tokens.
+    ///
+    /// Each role is assumed with the credentials of the previous one, starting
+    /// from the configured credentials, for hop-through setups such as a
+    /// landing account role followed by a workload account role.
+    ///
+    /// # Arguments
+    /// * `roles` - The roles to assume, in order
+    #[must_use]
+    pub fn assume_role_chain<I, R>(mut self, roles: I) -> Self
+    where
+        I: IntoIterator<Item = R>,
+        R: Into<AssumeRole>,
+    {
+        self.signer.assume_roles = roles.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets how long before their expiry cached credentials are resolved again.
+    ///
+    /// The buffer applies to the signer's credentials cache and to the identity
+    /// cache of the loaded AWS configuration, so tokens are never signed with
+    /// credentials about to expire. Defaults to no buffer.
+    ///
+    /// # Arguments
+    /// * `buffer_time` - The time before expiry at which credentials are refreshed
+    #[must_use]
+    pub fn credential_cache_buffer_time(mut self, buffer_time: impl Into<Duration>) -> Self {
+        self.signer.credential_cache_buffer_time = Some(buffer_time.into());
+        self
+    }
+
+    /// Sets how long credentials without an expiry are cached.
+    ///
+    /// Long-term credentials are cached indefinitely by default; with a TTL,
+    /// long-running services re-run the provider chain periodically and pick up
+    /// rotated keys. The TTL is also the default expiration of the identity
+    /// cache of the loaded AWS configuration.
+    ///
+    /// # Arguments
+    /// * `ttl` - The maximum age of credentials without an expiry
+    #[must_use]
+    pub fn credential_cache_ttl(mut self, ttl: impl Into<Duration>) -> Self {
+        self.signer.credential_cache_ttl = Some(ttl.into());
+        self
+    }
+
+    /// Sets the STS endpoint used for assuming roles and web identity federation.
+    ///
+    /// Use [`StsEndpoint::Url`] to reach STS through a private VPC endpoint in
+    /// deployments without internet access.
+    ///
+    /// # Arguments
+    /// * `endpoint` - The regional, global, or custom STS endpoint
+    #[must_use]
+    pub fn sts_endpoint(mut self, endpoint: StsEndpoint) -> Self {
+        self.signer.sts_endpoint = Some(endpoint);
+        self
+    }
+
+    /// Returns `MissingRegion` instead of signing for `us-east-1` when no region is found.
+    ///
+    /// Without a configured region, a region in the RDS hostname, or a region
+    /// in the AWS configuration, tokens are otherwise signed for `us-east-1`
+    /// and rejected by databases in other regions.
+    ///
+    /// # Arguments
+    /// * `strict_region` - Whether a missing region is an error
+    #[must_use]
+    pub const fn strict_region(mut self, strict_region: bool) -> Self {
+        self.signer.strict_region = strict_region;
+        self
+    }
+
+    /// Uses FIPS endpoints for STS, SSO, and other requests made while resolving credentials.
+    ///
+    /// RDS instance endpoints are unaffected, since they have no FIPS
+    /// hostnames. A global or custom [`StsEndpoint`] takes precedence for STS.
+    ///
+    /// # Arguments
+    /// * `use_fips` - Whether to use FIPS endpoints
+    #[must_use]
+    pub const fn use_fips(mut self, use_fips: bool) -> Self {
+        self.signer.endpoint_variants.fips = use_fips;
+        self
+    }
+
+    /// Uses dual-stack (IPv4 and IPv6) endpoints for requests made while resolving credentials.
+    ///
+    /// RDS instance endpoints are unaffected, since the same hostname serves
+    /// IPv4 and IPv6 clients. A global or custom [`StsEndpoint`] takes
+    /// precedence for STS.
+    ///
+    /// # Arguments
+    /// * `use_dualstack` - Whether to use dual-stack endpoints
+    #[must_use]
+    pub const fn use_dualstack(mut self, use_dualstack: bool) -> Self {
+        self.signer.endpoint_variants.dual_stack = use_dualstack;
+        self
+    }
+
+    /// Sets the HTTP client used to resolve credentials.
+    ///
+    /// The client is used for STS, SSO, and container credential requests, so
+    /// proxies and custom certificate authorities can be configured on it (e.g.,
+    /// with `aws_smithy_http_client::Builder`). With [`Self::sdk_config`], the
+    /// pre-loaded credentials provider keeps the HTTP client it was built with.
+    ///
+    /// # Arguments
+    /// * `http_client` - The HTTP client for credential requests
+    #[must_use]
+    pub fn http_client(mut self, http_client: impl HttpClient + 'static) -> Self {
+        self.signer.http_client = Some(SharedHttpClient::new(http_client));
+        self
+    }
+
+    /// Sets how credentials that expire before the token are handled.
+    ///
+    /// Defaults to [`ExpiryBehavior::Warn`], which signs the token unchanged and
+    /// logs a warning through `tracing`.
+    ///
+    /// # Arguments
+    /// * `behavior` - Whether to warn, clamp the token lifetime, or fail
+    #[must_use]
+    pub const fn expiry_behavior(mut self, behavior: ExpiryBehavior) -> Self {
+        self.signer.expiry_behavior = behavior;
+        self
+    }
+
+    /// Sets the database username.
+    ///
+    /// # Arguments
+    /// * `user` - The database user to authenticate as
+    #[must_use]
+    pub fn user(mut self, user: impl Into<String>) -> Self {
+        self.signer.user = user.into();
+        self
+    }
+
+    /// Builds the final [`Signer`] instance.
+    #[must_use]
+    pub fn build(self) -> Signer {
+        self.signer
+    }
+
+    /// Validates the configuration and builds the final [`Signer`] instance.
+    ///
+    /// Unlike [`Self::build`], this rejects a host that was never set or is not
+    /// a bare hostname, port 0, an empty user, a malformed region, and a token
+    /// lifetime outside 1 second to 15 minutes.
+    ///
+    /// # Errors
+    /// * `ValidationError` - Listing every invalid field
+    pub fn try_build(self) -> Result<Signer, super::Error> {
+        let mut errors = ValidationError::default();
+        let signer = &self.signer;
+        if !self.host_set {
+            errors.push(Field::Host, "was not set");
+        } else if let Some(reason) = validation::hostname_error(&signer.host) {
+            errors.push(Field::Host, format!("`{}` {reason}", signer.host));
+        }
+        if let Some(connect_host) = &signer.connect_host {
+            if let Some(reason) = validation::hostname_error(connect_host) {
+                errors.push(
+                    Field::Host,
+                    format!("connect host `{connect_host}` {reason}"),
+                );
+            }
+        }
+        if signer.port == 0 {
+            errors.push(Field::Port, "must not be 0");
+        }
+        if signer.user.is_empty() {
+            errors.push(Field::User, "must not be empty");
+        }
+        if let Some(region) = signer
+            .region
+            .as_ref()
+            .map(AsRef::as_ref)
+            .filter(|r| !validation::is_region(r))
+        {
+            errors.push(
+                Field::Region,
+                format!("`{region}` is not an AWS region (e.g., `us-east-1`)"),
+            );
+        }
+        if signer.expires_in < Duration::from_secs(1) || signer.expires_in > MAX_EXPIRES_IN {
+            errors.push(
+                Field::ExpiresIn,
+                format!(
+                    "{:?} is outside the allowed range of 1s to {MAX_EXPIRES_IN:?}",
+                    signer.expires_in
+                ),
+            );
+        }
+        errors
+            .into_result()
+            .map_err(super::Error::ValidationError)?;
+        Ok(self.signer)
+    }
+}
+
+impl Signer {
+    /// Creates a new `SignerBuilder` for configuring a Signer instance.
+    #[must_use]
+    pub fn builder() -> SignerBuilder {
+        SignerBuilder::new()
+    }
+
+    /// Creates a `SignerBuilder` seeded from a pre-loaded AWS SDK configuration.
+    ///
+    /// The builder signs with the configuration's credentials provider, as with
+    /// [`SignerBuilder::sdk_config`], and for its region, which takes precedence
+    /// over a region inferred from the hostname unless set again with
+    /// [`SignerBuilder::region`].
+    ///
+    /// # Arguments
+    /// * `sdk_config` - The shared AWS configuration (e.g., from `aws_config::load_defaults`)
+    #[must_use]
+    pub fn from_sdk_config(sdk_config: &SdkConfig) -> SignerBuilder {
+        let builder = Self::builder().sdk_config(sdk_config);
+        match sdk_config.region() {
+            Some(region) => builder.region(region),
+            None => builder,
+        }
+    }
+
+    /// Creates a signer for an RDS for `PostgreSQL` instance on the default port 5432.
+    ///
+    /// Credentials and the region are resolved as for [`SignerBuilder::build`];
+    /// use [`Self::to_builder`] to change other settings.
+    ///
+    /// # Arguments
+    /// * `host` - The RDS endpoint (e.g., "mydb.123456789012.us-east-1.rds.amazonaws.com")
+    /// * `user` - The database user to authenticate as
+    #[must_use]
+    pub fn postgres(host: impl Into<String>, user: impl Into<String>) -> Self {
+        Self::builder()
+            .host(host)
+            .user(user)
+            .engine(Engine::Postgres)
+            .build()
+    }
+
+    /// Creates a signer for an RDS for `MySQL` instance on the default port 3306.
+    ///
+    /// Credentials and the region are resolved as for [`SignerBuilder::build`];
+    /// use [`Self::to_builder`] to change other settings.
+    ///
+    /// # Arguments
+    /// * `host` - The RDS endpoint (e.g., "mydb.123456789012.us-east-1.rds.amazonaws.com")
+    /// * `user` - The database user to authenticate as
+    #[must_use]
+    pub fn mysql(host: impl Into<String>, user: impl Into<String>) -> Self {
+        Self::builder()
+            .host(host)
+            .user(user)
+            .engine(Engine::MySql)
+            .build()
+    }
+
+    /// Creates a signer from a plain [`SignerConfig`].
+    ///
+    /// Unlike `Signer::from(config)`, the result is validated with
+    /// [`SignerBuilder::try_build`].
+    ///
+    /// # Errors
+    /// * `ValidationError` - If the configuration is invalid
+    pub fn from_config(config: &SignerConfig) -> Result<Self, super::Error> {
+        config.builder().try_build()
+    }
+
+    /// Creates a signer from a database URL such as `DATABASE_URL`.
+    ///
+    /// The host, port, user, and region are taken from the URL as described for
+    /// [`SignerBuilder::url`], and the result is validated with
+    /// [`SignerBuilder::try_build`]. Use the builder to configure credentials.
+    ///
+    /// # Arguments
+    /// * `database_url` - The URL (e.g., `postgres://iam_user@mydb.xxxx.us-east-1.rds.amazonaws.com:5432/app`)
+    ///
+    /// # Errors
+    /// * `ParseError` - If the URL is malformed, has no host or user, or uses an unsupported scheme
+    /// * `ValidationError` - If the extracted settings are invalid
+    pub fn from_url(database_url: &str) -> Result<Self, super::Error> {
+        Self::from_database_url(DatabaseUrl::parse(database_url)?)
+    }
+
+    /// Creates a validated signer from a parsed database URL with a user.
+    pub(crate) fn from_database_url(database_url: DatabaseUrl) -> Result<Self, super::Error> {
+        if database_url.user.is_none() {
+            return Err(super::Error::ParseError(
+                "database URL has no user".to_string(),
+            ));
+        }
+        Self::builder().database_url(database_url).try_build()
+    }
+
+    /// Returns a signer for another database user on the same instance.
+    ///
+    /// The new signer keeps every other setting and shares the credentials
+    /// cache with `self`, so services connecting as several IAM database users
+    /// resolve credentials once rather than once per user.
+    ///
+    /// # Arguments
+    /// * `user` - The database user to authenticate as (e.g., "readonly")
+    #[must_use]
+    pub fn with_user(&self, user: impl Into<String>) -> Self {
+        Self {
+            user: user.into(),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a signer for another host and port that shares the credentials cache.
+    ///
+    /// The connect host is cleared, since it belongs to the original endpoint.
+    pub(crate) fn with_endpoint(&self, host: impl Into<String>, port: u16) -> Self {
+        Self {
+            host: host.into(),
+            connect_host: None,
+            port,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a builder holding this signer's configuration.
+    ///
+    /// The resulting signer starts with an empty credentials cache, since the
+    /// changed settings may select different credentials or another region.
+    #[must_use]
+    pub fn to_builder(&self) -> SignerBuilder {
+        SignerBuilder {
+            signer: self.with_cache(Arc::default()),
+            host_set: true,
+            port_set: true,
+        }
+    }
+
+    /// Copies the configuration of this signer, using `credentials_cache` as the credentials cache.
+    fn with_cache(&self, credentials_cache: Arc<CredentialsCache>) -> Self {
+        Self {
+            expires_in: self.expires_in,
+            host: self.host.clone(),
+            connect_host: self.connect_host.clone(),
+            port: self.port,
+            engine: self.engine,
+            user: self.user.clone(),
+            region: self.region.clone(),
+            service_name: self.service_name.clone(),
+            extra_params: self.extra_params.clone(),
+            sdk_config: self.sdk_config.clone(),
+            identity: self.identity.clone(),
+            credentials_provider: self.credentials_provider.clone(),
+            web_identity: self.web_identity.clone(),
+            container_credentials_uri: self.container_credentials_uri.clone(),
+            container_authorization_token_file: self.container_authorization_token_file.clone(),
+            credential_process: self.credential_process.clone(),
+            credential_process_timeout: self.credential_process_timeout,
+            profile: self.profile.clone(),
+            sso: self.sso,
+            credential_timeout: self.credential_timeout,
+            credential_retry_config: self.credential_retry_config.clone(),
+            disable_imds: self.disable_imds,
+            fail_fast: self.fail_fast,
+            strict_region: self.strict_region,
+            expiry_behavior: self.expiry_behavior,
+            expires_in_limit: self.expires_in_limit,
+            sts_endpoint: self.sts_endpoint.clone(),
+            endpoint_variants: self.endpoint_variants,
+            http_client: self.http_client.clone(),
+            assume_roles: self.assume_roles.clone(),
+            credential_cache_buffer_time: self.credential_cache_buffer_time,
+            credential_cache_ttl: self.credential_cache_ttl,
+            credentials_cache,
+        }
+    }
+
+    /// Sets the RDS instance hostname, e.g., after an endpoint failover.
+    ///
+    /// A port embedded in the host is split off as with [`SignerBuilder::host`].
+    /// Clears the credentials cache of this signer, since the signing region
+    /// may be inferred from the hostname.
+    ///
+    /// # Arguments
+    /// * `host` - The RDS endpoint (e.g., "mydb.123456789012.us-east-1.rds.amazonaws.com")
+    pub fn set_host(&mut self, host: impl Into<String>) {
+        let host = host.into();
+        if let Some((host, port)) = database_url::split_port(&host) {
+            self.host = host;
+            self.port = port;
+        } else {
+            self.host = validation::unbracket_host(host);
+        }
+        self.credentials_cache = Arc::default();
+    }
+
+    /// Sets the database port number.
+    ///
+    /// # Arguments
+    /// * `port` - The port number (e.g., 5432 for `PostgreSQL`)
+    pub fn set_port(&mut self, port: impl Into<u16>) {
+        self.port = port.into();
+    }
+
+    /// Sets the database username.
+    ///
+    /// # Arguments
+    /// * `user` - The database user to authenticate as
+    pub fn set_user(&mut self, user: impl Into<String>) {
+        self.user = user.into();
+    }
+
+    /// Sets the AWS region the token is signed for.
+    ///
+    /// Clears the credentials cache of this signer, which also holds the region.
+    ///
+    /// # Arguments
+    /// * `region` - The AWS region (e.g., "us-east-1" or a [`Region`])
+    pub fn set_region(&mut self, region: impl IntoRegion) {
+        self.region = Some(region.into_region());
+        self.credentials_cache = Arc::default();
+    }
+
+    /// Sets the token expiration duration.
+    ///
+    /// # Arguments
+    /// * `expires_in` - The duration for which the token will be valid
+    pub fn set_expires_in(&mut self, expires_in: impl Into<Duration>) {
+        self.expires_in = expires_in.into();
+    }
+
+    /// Returns the configuration compared by `PartialEq` and `Hash`.
+    fn key(&self) -> impl Eq + Hash + '_ {
+        (
+            (
+                self.expires_in,
+                &self.host,
+                &self.connect_host,
+                self.port,
+                self.engine,
+                &self.user,
+                &self.region,
+                &self.service_name,
+                &self.extra_params,
+            ),
+            (
+                self.sdk_config.is_some(),
+                self.identity.is_some(),
+                self.credentials_provider.is_some(),
+                &self.web_identity,
+                &self.container_credentials_uri,
+                &self.container_authorization_token_file,
+            ),
+            (
+                &self.credential_process,
+                self.credential_process_timeout,
+                &self.profile,
+                self.sso,
+                self.credential_timeout,
+                self.credential_retry_config.as_ref().map(|retry_config| {
+                    (
+                        retry_config.max_attempts(),
+                        retry_config.initial_backoff(),
+                        retry_config.max_backoff(),
+                        retry_config.use_static_exponential_base(),
+                    )
+                }),
+            ),
+            (
+                self.disable_imds,
+                self.fail_fast,
+                self.strict_region,
+                self.expiry_behavior,
+                self.expires_in_limit,
+                &self.sts_endpoint,
+                self.endpoint_variants,
+                self.http_client.is_some(),
+            ),
+            (
+                &self.assume_roles,
+                self.credential_cache_buffer_time,
+                self.credential_cache_ttl,
+            ),
+        )
+    }
+
+    /// Returns the RDS instance hostname tokens are signed for.
+    #[must_use]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns the host clients dial: the connect host if set, otherwise the signed host.
+    #[must_use]
+    pub fn connect_host(&self) -> &str {
+        self.connect_host.as_deref().unwrap_or(&self.host)
+    }
+
+    /// Returns the database port.
+    #[must_use]
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns the database user.
+    #[must_use]
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Returns the configured AWS region.
+    ///
+    /// `None` if the region is inferred from the hostname or the AWS configuration.
+    #[must_use]
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_ref().map(AsRef::as_ref)
+    }
+
+    /// Returns the token lifetime, before any clamping to the RDS maximum.
+    #[must_use]
+    pub const fn expires_in(&self) -> Duration {
+        self.expires_in
+    }
+
+    /// Returns the database engine, if one was configured or taken from a database URL.
+    #[must_use]
+    pub const fn engine(&self) -> Option<Engine> {
+        self.engine
+    }
+
+    /// Returns the service name tokens are signed for.
+    pub(crate) fn service_name(&self) -> &str {
+        &self.service_name
+    }
+
+    /// Returns the additional query parameters added to tokens.
+    pub(crate) fn extra_params(&self) -> &[(String, String)] {
+        &self.extra_params
+    }
+
+    /// Returns where credentials are configured to come from, e.g. `profile:prod`.
+    ///
+    /// Custom providers and the default chain return `None`; only the
+    /// credentials they resolve identify them.
+    pub(crate) fn credential_source_id(&self) -> Option<String> {
+        if self.identity.is_some() || self.credentials_provider.is_some() {
+            None
+        } else if let Some(web_identity) = &self.web_identity {
+            Some(format!("web_identity:{}", web_identity.role_arn))
+        } else if let Some(uri) = &self.container_credentials_uri {
+            Some(format!("container:{uri}"))
+        } else if let Some(command) = &self.credential_process {
+            Some(format!("process:{command}"))
+        } else {
+            self.profile
+                .as_ref()
+                .map(|profile| format!("profile:{profile}"))
+        }
+    }
+
+    /// Returns the ARNs of the roles assumed before signing, in order.
+    pub(crate) fn role_arns(&self) -> impl Iterator<Item = &str> {
+        self.assume_roles.iter().map(AssumeRole::role_arn)
+    }
+
+    /// Creates a signer from `RDS_SIGNER_*` environment variables.
+    ///
+    /// Equivalent to [`Self::from_env_with_prefix`] with the `RDS_SIGNER` prefix.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::from_env_with_prefix`].
+    pub fn from_env() -> Result<Self, super::Error> {
+        Self::from_env_with_prefix("RDS_SIGNER")
+    }
+
+    /// Creates a signer from environment variables with the given prefix.
+    ///
+    /// Reads `{prefix}_HOST` and `{prefix}_USER` (required), and `{prefix}_PORT`,
+    /// `{prefix}_REGION`, and `{prefix}_EXPIRES_IN` (in seconds), which fall back
+    /// to the builder defaults. The result is validated with
+    /// [`SignerBuilder::try_build`].
+    ///
+    /// # Arguments
+    /// * `prefix` - The variable name prefix (e.g., "DB" for `DB_HOST`)
+    ///
+    /// # Errors
+    /// * `EnvVarError` - If a required variable is missing or a value cannot be parsed
+    /// * `ValidationError` - If the resulting configuration is invalid
+    pub fn from_env_with_prefix(prefix: &str) -> Result<Self, super::Error> {
+        Self::builder().env(prefix, &Env::real())?.try_build()
+    }
+
+    /// Creates a signer for a named database defined in a TOML configuration file.
+    ///
+    /// The file lists databases under `[databases.<name>]` with a `host` and
+    /// `user`, and optionally a `port`, `region`, `expires_in` (seconds), and
+    /// AWS `profile`. The result is validated with [`SignerBuilder::try_build`].
+    ///
+    /// # Arguments
+    /// * `path` - The path to the configuration file
+    /// * `name` - The database to configure (e.g., "analytics")
+    ///
+    /// # Errors
+    /// * `ParseError` - If the file cannot be read or parsed, or does not define `name`
+    /// * `ValidationError` - If the database configuration is invalid
+    #[cfg(feature = "config-file")]
+    pub fn from_config_file(
+        path: impl AsRef<std::path::Path>,
+        name: &str,
+    ) -> Result<Self, super::Error> {
+        crate::config_file::ConfigFile::load(path.as_ref())?
+            .configure(name, Self::builder())?
+            .try_build()
+    }
+
+    /// Loads the AWS configuration used when no pre-loaded configuration was provided.
+    async fn load_sdk_config(&self) -> SdkConfig {
+        let mut loader = aws_config::defaults(behavior_version());
+        if let Some(profile) = &self.profile {
+            loader = loader.profile_name(profile);
+        }
+        if let Some(http_client) = &self.http_client {
+            loader = loader.http_client(http_client.clone());
+        }
+        if self.credential_cache_buffer_time.is_some() || self.credential_cache_ttl.is_some() {
+            let mut identity_cache = IdentityCache::lazy();
+            if let Some(buffer_time) = self.credential_cache_buffer_time {
+                identity_cache = identity_cache.buffer_time(buffer_time);
+            }
+            if let Some(ttl) = self.credential_cache_ttl {
+                identity_cache = identity_cache.default_expiration(ttl.min(MAX_IDENTITY_CACHE_TTL));
+            }
+            loader = loader.identity_cache(identity_cache.build());
+        }
+        if self.disable_imds || self.fail_fast {
+            loader = credentials::without_imds(
+                loader,
+                self.profile.as_deref(),
+                self.http_client.as_ref(),
+                self.endpoint_variants,
+                self.fail_fast,
+            )
+            .await;
+        }
+        if self.endpoint_variants.fips {
+            loader = loader.use_fips(true);
+        }
+        if self.endpoint_variants.dual_stack {
+            loader = loader.use_dual_stack(true);
+        }
+        loader.load().await
+    }
+
+    /// Returns the cached credentials and region, resolving them again once the credentials expire.
+    ///
+    /// Credentials that have already expired when the provider returns them are
+    /// rejected rather than cached, so no token is signed with a dead session.
+    /// Concurrent requests that miss the cache share a single resolution, so
+    /// a burst of token requests, e.g. while a pool warms up, makes one pass
+    /// through the credential chain.
+    async fn resolve_cached(&self) -> Result<Resolved, super::Error> {
+        if let Some(resolved) = self.cached_resolved() {
+            return Ok(resolved);
+        }
+        let _resolving = self.credentials_cache.resolving.lock().await;
+        // Another request may have resolved the credentials while this one waited.
+        if let Some(resolved) = self.cached_resolved() {
+            return Ok(resolved);
+        }
+        let resolved = match self.credential_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, self.resolve())
+                .await
+                .map_err(|_| super::Error::TimeoutError(timeout))??,
+            None => self.resolve().await?,
+        };
+        if let Some(expiry) = resolved.credentials.expiry() {
+            if expiry <= SystemTime::now() {
+                return Err(super::Error::CredentialExpiryError(
+                    "the credentials provider returned expired credentials".to_string(),
+                ));
+            }
+        }
+        *self
+            .credentials_cache
+            .resolved
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(resolved.clone());
+        Ok(resolved)
+    }
+
+    /// Returns the cached credentials and region if the credentials are still valid.
+    fn cached_resolved(&self) -> Option<Resolved> {
+        self.credentials_cache
+            .resolved
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+            .filter(|resolved| resolved.is_valid_at(SystemTime::now()))
+    }
+
+    /// Resolves credentials and the signing region from the configured sources.
+    async fn resolve(&self) -> Result<Resolved, super::Error> {
+        let loaded;
+        let config = match (&self.sdk_config, &self.http_client) {
+            (Some(config), None) if !self.endpoint_variants.any() => config,
+            (Some(config), http_client) => {
+                let mut builder = config.to_builder();
+                self.endpoint_variants.apply(&mut builder);
+                if let Some(http_client) = http_client {
+                    builder.set_http_client(Some(http_client.clone()));
+                }
+                loaded = builder.build();
+                &loaded
+            }
+            (None, _) => {
+                loaded = self.load_sdk_config().await;
+                &loaded
+            }
+        };
+        let region = match self.explicit_region() {
+            Some(region) => region,
+            None => self.fallback_region(config.region())?,
+        };
+        let sts_config = credentials::sts_config(config, &region, self.sts_endpoint.as_ref());
+        let mut provider = if let Some(identity) = &self.identity {
+            Some(SharedCredentialsProvider::new(credentials::from_identity(
+                identity,
+            )?))
+        } else if let Some(provider) = &self.credentials_provider {
+            Some(provider.clone())
+        } else if let Some(web_identity) = &self.web_identity {
+            Some(web_identity.provider(&sts_config))
+        } else if let Some(uri) = &self.container_credentials_uri {
+            let endpoint = ContainerEndpoint::new(
+                uri.clone(),
+                self.container_authorization_token_file.clone(),
+                &Env::real(),
+            );
+            Some(SharedCredentialsProvider::new(endpoint.provider(config)?))
+        } else if let Some(command) = &self.credential_process {
+            Some(SharedCredentialsProvider::new(CredentialProcess::new(
+                command.clone(),
+                self.credential_process_timeout,
+            )))
+        } else if let Some(profile) = &self.profile {
+            Some(
+                credentials::profile_provider(
+                    profile,
+                    &region,
+                    self.credential_process_timeout,
+                    self.http_client.as_ref(),
+                    &sts_config,
+                    &Fs::real(),
+                    &Env::real(),
+                )
+                .await,
+            )
+        } else {
+            config.credentials_provider()
+        }
+        .ok_or_else(|| {
+            super::Error::NoCredentialsError("no credentials provider found".to_string())
+        })?;
+        for role in &self.assume_roles {
+            provider = role.provider(&sts_config, provider).await;
+        }
+        let credentials = self.provide_credentials(&provider).await.map_err(|e| {
+            credentials::resolution_error(&e, self.profile.as_deref().filter(|_| self.sso))
+        })?;
+        let refresh_at = credentials
+            .expiry()
+            // Credentials whose TTL runs past the end of time never expire.
+            .or_else(|| SystemTime::now().checked_add(self.credential_cache_ttl?))
+            .map(|expiry| {
+                let buffer_time = self.credential_cache_buffer_time.unwrap_or_default();
+                expiry.checked_sub(buffer_time).unwrap_or(expiry)
+            });
+        Ok(Resolved {
+            credentials,
+            region,
+            refresh_at,
+        })
+    }
+
+    /// Requests credentials from `provider`, retrying provider errors as configured.
+    async fn provide_credentials(
+        &self,
+        provider: &SharedCredentialsProvider,
+    ) -> aws_credential_types::provider::Result {
+        let Some(retry_config) = &self.credential_retry_config else {
+            return provider.provide_credentials().await;
+        };
+        let mut backoff = retry_config.initial_backoff();
+        let mut attempt = 1;
+        loop {
+            match provider.provide_credentials().await {
+                Err(CredentialsError::ProviderError(_))
+                    if attempt < retry_config.max_attempts() =>
+                {
+                    let delay = if retry_config.use_static_exponential_base() {
+                        backoff
+                    } else {
+                        refresh_schedule::random_duration(backoff)
+                    };
+                    tokio::time::sleep(delay).await;
+                    backoff = backoff.saturating_mul(2).min(retry_config.max_backoff());
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Generates an authentication token for connecting to the RDS instance.
+    ///
+    /// This method will use the configured AWS credentials to generate a signed
+    /// authentication token that can be used to connect to the RDS instance.
+    /// The token is valid for the duration specified in the configuration.
+    ///
+    /// Credentials are resolved on the first call and reused by later calls
+    /// until they expire, so subsequent tokens are generated without I/O.
+    /// [`SignerBuilder::credential_retry_config`] applies to that resolution
+    /// only: signing is a local computation that cannot fail transiently, so
+    /// its errors are returned without retrying.
+    ///
+    /// # Returns
+    /// * `Ok(AuthToken)` - The authentication token with its expiry, region, and user
+    /// * `Err(Error)` - If token generation fails
+    ///
+    /// # Errors
+    /// * `SignerError` - If resolving credentials or signing the request fails
+    /// * `SsoSessionError` - If the SSO session of the configured profile is missing or expired
+    /// * `NoCredentialsError` - If no credentials provider supplied credentials
+    /// * `CredentialExpiryError` - If the credentials have already expired, or expire before the token and [`ExpiryBehavior::Error`] is set
+    /// * `TimeoutError` - If credential resolution exceeds the configured timeout
+    /// * `MissingRegion` - If strict region mode is enabled and no region is found
+    /// * `ValidationError` - If the host contains a colon but is not an IPv6 address, e.g. an invalid port
+    /// * `ParseError` - If URL parsing fails
+    pub async fn fetch_token(&self) -> Result<AuthToken, super::Error> {
+        let (credentials, region) = self.resolve_for_signing().await?;
+        self.sign_token(credentials, &region)
+    }
+
+    /// Returns the cached credentials, resolving them if needed, and the region to sign for.
+    ///
+    /// Signers sharing the credentials cache may be for endpoints in other
+    /// regions, so this signer's configured or inferred region takes
+    /// precedence over the region resolved with the credentials.
+    pub(crate) async fn resolve_for_signing(&self) -> Result<(Credentials, String), super::Error> {
+        let Resolved {
+            credentials,
+            region,
+            ..
+        } = self.resolve_cached().await?;
+        let region = self.explicit_region().unwrap_or(region);
+        Ok((credentials, region))
+    }
+
+    /// Generates an authentication token together with its expiry and that of the credentials.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::fetch_token`].
+    #[deprecated(note = "`fetch_token` now returns the token with its expiry")]
+    pub async fn fetch_token_with_expiry(&self) -> Result<AuthToken, super::Error> {
+        self.fetch_token().await
+    }
+
+    /// Generates an authentication token signed with the given credentials.
+    ///
+    /// The configured credential sources are not consulted, so one signer can
+    /// sign for different identities, such as per-tenant credentials in a
+    /// multi-tenant service. The region is the configured one, or otherwise
+    /// the region of the AWS configuration.
+    ///
+    /// # Errors
+    /// * `CredentialExpiryError` - If the credentials have already expired, or expire before the token and [`ExpiryBehavior::Error`] is set
+    /// * `MissingRegion` - If strict region mode is enabled and no region is found
+    /// * `SignerError` - If signing the request fails
+    /// * `ParseError` - If URL parsing fails
+    pub async fn fetch_token_with_credentials(
+        &self,
+        credentials: &Credentials,
+    ) -> Result<AuthToken, super::Error> {
+        if credentials
+            .expiry()
+            .is_some_and(|expiry| expiry <= SystemTime::now())
+        {
+            return Err(super::Error::CredentialExpiryError(
+                "the provided credentials have expired".to_string(),
+            ));
+        }
+        let region = self.signing_region().await?;
+        self.sign_token(credentials.clone(), &region)
+    }
+
+    /// Returns the configured region, or the region in an RDS endpoint hostname.
+    ///
+    /// Tokens must be signed for the region of the database, so the hostname
+    /// takes precedence over the region of the AWS configuration.
+    pub(crate) fn explicit_region(&self) -> Option<String> {
+        self.region
+            .as_ref()
+            .map(ToString::to_string)
+            .or_else(|| database_url::rds_region(&self.host))
+    }
+
+    /// Returns the configured region, falling back to the region of the AWS configuration.
+    async fn signing_region(&self) -> Result<String, super::Error> {
+        if let Some(region) = self.explicit_region() {
+            return Ok(region);
+        }
+        match &self.sdk_config {
+            Some(config) => self.fallback_region(config.region()),
+            None => self.fallback_region(self.load_sdk_config().await.region()),
+        }
+    }
+
+    /// Returns the region of the AWS configuration, or `us-east-1` unless strict region mode is enabled.
+    fn fallback_region(&self, region: Option<&Region>) -> Result<String, super::Error> {
+        match region {
+            Some(region) => Ok(region.to_string()),
+            None if self.strict_region => Err(super::Error::MissingRegion),
+            None => Ok("us-east-1".to_string()),
+        }
+    }
+
+    /// Signs a token for `region` with `credentials`.
+    pub(crate) fn sign_token(
+        &self,
+        credentials: Credentials,
+        region: &str,
+    ) -> Result<AuthToken, super::Error> {
+        let now = SystemTime::now();
+        let credentials_expire_at = credentials.expiry();
+        let expires_in = self.expires_in_at(now, credentials_expire_at)?;
+        let identity = credentials.into();
+
+        let mut signing_settings = SigningSettings::default();
+        signing_settings.expires_in = Some(expires_in);
+        signing_settings.signature_location =
+            aws_sigv4::http_request::SignatureLocation::QueryParams;
+
+        let signing_params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region(region)
+            .name(&self.service_name)
+            .time(now)
+            .settings(signing_settings)
+            .build()
+            .map_err(|e| super::Error::SignerError(e.to_string()))?;
+
+        let hostname = if self.host.parse::<Ipv6Addr>().is_ok() {
+            format!("[{}]", self.host)
+        } else if self.host.contains(':') {
+            // A colon outside an IPv6 literal would otherwise produce an invalid signed URL.
+            let reason = validation::hostname_error(&self.host).unwrap_or("is not a valid host");
+            let mut errors = ValidationError::default();
+            errors.push(Field::Host, format!("`{}` {reason}", self.host));
+            return Err(super::Error::ValidationError(errors));
+        } else {
+            self.host.clone()
+        };
+        let mut url = format!(
+            "https://{hostname}:{port}/?Action=connect&DBUser={username}",
+            port = self.port,
+            username = self.user
+        );
+        for (name, value) in &self.extra_params {
+            url.push('&');
+            url.extend(utf8_percent_encode(name, QUERY_ENCODE_SET));
+            url.push('=');
+            url.extend(utf8_percent_encode(value, QUERY_ENCODE_SET));
+        }
+
+        let signable_request =
+            SignableRequest::new("GET", &url, std::iter::empty(), SignableBody::Bytes(&[]))
+                .map_err(|e| super::Error::SignerError(e.to_string()))?;
+
+        let (signing_instructions, signature) = sign(signable_request, &signing_params.into())
+            .map_err(|e| super::Error::SignerError(e.to_string()))?
+            .into_parts();
+        let (_, params) = signing_instructions.into_parts();
+
+        let mut url = url::Url::parse(&url).map_err(|e| super::Error::ParseError(e.to_string()))?;
+        for (name, value) in &params {
+            url.query_pairs_mut().append_pair(name, value);
+        }
+
+        let response = String::from(url).split_off("https://".len());
+        #[cfg(feature = "zeroize")]
+        zeroize_signing_output(signature, params);
+        #[cfg(not(feature = "zeroize"))]
+        let _ = (signature, params);
+
+        Ok(AuthToken {
+            value: response,
+            signed_at: now,
+            expires_at: now + expires_in,
+            credentials_expire_at,
+            region: region.to_string(),
+            host: self.connect_host().to_string(),
+            port: self.port,
+            user: self.user.clone(),
+        })
+    }
+
+    /// Returns the token lifetime at `now`, applying the expiry behavior to credentials
+    /// that expire before the configured lifetime ends.
+    fn expires_in_at(
+        &self,
+        now: SystemTime,
+        credentials_expire_at: Option<SystemTime>,
+    ) -> Result<Duration, super::Error> {
+        let expires_in = self.limited_expires_in()?;
+        let Some(remaining) = credentials_expire_at
+            .and_then(|expiry| expiry.duration_since(now).ok())
+            .filter(|remaining| *remaining < expires_in)
+        else {
+            return Ok(expires_in);
+        };
+        match self.expiry_behavior {
+            ExpiryBehavior::Warn => {
+                tracing::warn!(
+                    credentials_expire_in = ?remaining,
+                    ?expires_in,
+                    "credentials expire before the RDS token; the token will be rejected early"
+                );
+                Ok(expires_in)
+            }
+            ExpiryBehavior::Clamp => Ok(Duration::from_secs(remaining.as_secs().max(1))),
+            ExpiryBehavior::Error => Err(super::Error::CredentialExpiryError(format!(
+                "credentials expire in {remaining:?}, before the token lifetime of {expires_in:?}"
+            ))),
+        }
+    }
+
+    /// Returns the configured token lifetime, applying the RDS maximum.
+    fn limited_expires_in(&self) -> Result<Duration, super::Error> {
+        if self.expires_in <= MAX_EXPIRES_IN {
+            return Ok(self.expires_in);
+        }
+        match self.expires_in_limit {
+            ExpiresInLimit::Clamp => {
+                tracing::warn!(
+                    expires_in = ?self.expires_in,
+                    max = ?MAX_EXPIRES_IN,
+                    "token lifetime exceeds the RDS maximum; clamping"
+                );
+                Ok(MAX_EXPIRES_IN)
+            }
+            ExpiresInLimit::Error => {
+                let mut errors = ValidationError::default();
+                errors.push(
+                    Field::ExpiresIn,
+                    format!(
+                        "{:?} exceeds the RDS maximum of {MAX_EXPIRES_IN:?}",
+                        self.expires_in
+                    ),
+                );
+                Err(super::Error::ValidationError(errors))
+            }
+        }
     }
 }
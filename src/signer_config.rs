@@ -0,0 +1,144 @@
+//! Plain signer configuration.
+//!
+//! [`SignerConfig`] holds the settings that describe which database a signer
+//! targets as a struct literal, so it can be declared in `const` and `static`
+//! items. With the `serde` feature it can also be embedded in application
+//! config structs loaded by serde-based config crates:
+//!
+//! ```toml
+//! [database]
+//! host = "mydb.xxxx.us-east-1.rds.amazonaws.com"
+//! port = 5432
+//! user = "app"
+//! region = "us-east-1"
+//! expires_in = 600
+//! ```
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::sign::{Signer, SignerBuilder};
+
+/// The plain configuration of a [`Signer`].
+///
+/// Strings are `Cow<'static, str>` so that configurations can be written as
+/// constants with `Cow::Borrowed`. With the `serde` feature, the token
+/// lifetime is (de)serialized as a number of seconds. Build a signer with
+/// [`SignerConfig::builder`] to add credential settings, or convert the
+/// configuration with [`Signer::from_config`] or `Signer::from`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct SignerConfig {
+    /// The RDS instance hostname.
+    pub host: Cow<'static, str>,
+    /// The database port, defaulting to 5432.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub port: Option<u16>,
+    /// The database user.
+    pub user: Cow<'static, str>,
+    /// The AWS region, inferred from the host or the AWS configuration if unset.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub region: Option<Cow<'static, str>>,
+    /// The token lifetime, defaulting to 15 minutes.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "duration_secs"
+        )
+    )]
+    pub expires_in: Option<Duration>,
+    /// The AWS profile used for credentials and region.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub profile: Option<Cow<'static, str>>,
+}
+
+impl SignerConfig {
+    /// Creates a configuration for `user` on `host`, leaving every other setting at its default.
+    ///
+    /// # Arguments
+    /// * `host` - The RDS endpoint (e.g., "mydb.123456789012.us-east-1.rds.amazonaws.com")
+    /// * `user` - The database user to authenticate as
+    #[must_use]
+    pub const fn new(host: &'static str, user: &'static str) -> Self {
+        Self {
+            host: Cow::Borrowed(host),
+            port: None,
+            user: Cow::Borrowed(user),
+            region: None,
+            expires_in: None,
+            profile: None,
+        }
+    }
+
+    /// Returns a builder configured with these settings.
+    #[must_use]
+    pub fn builder(&self) -> SignerBuilder {
+        self.configure(Signer::builder())
+    }
+
+    /// Applies these settings to `builder`.
+    pub(crate) fn configure(&self, mut builder: SignerBuilder) -> SignerBuilder {
+        builder = builder.host(self.host.as_ref()).user(self.user.as_ref());
+        if let Some(port) = self.port {
+            builder = builder.port(port);
+        }
+        if let Some(region) = &self.region {
+            builder = builder.region(region.as_ref());
+        }
+        if let Some(expires_in) = self.expires_in {
+            builder = builder.expires_in(expires_in);
+        }
+        if let Some(profile) = &self.profile {
+            builder = builder.profile(profile.as_ref());
+        }
+        builder
+    }
+}
+
+impl From<SignerConfig> for Signer {
+    /// Builds a signer with [`SignerBuilder::build`], without validation.
+    fn from(config: SignerConfig) -> Self {
+        config.builder().build()
+    }
+}
+
+/// Serializes an optional [`Duration`] as whole seconds.
+#[cfg(feature = "serde")]
+mod duration_secs {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    // serde's `with` attribute passes a reference to the field.
+    #[allow(clippy::ref_option)]
+    pub(super) fn serialize<S: Serializer>(
+        duration: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match duration {
+            Some(duration) => serializer.serialize_u64(duration.as_secs()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_secs))
+    }
+}
@@ -0,0 +1,263 @@
+//! Reuse of tokens across connections.
+//!
+//! Signing is cheap, but services that open many connections per second
+//! still pay for it, and for resolving credentials, on every connect.
+//! [`TokenCache`] hands out the same token until it nears expiry.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, SystemTime};
+
+use crate::cache_stats::{CacheStats, StatsRecorder};
+use crate::sign::{AuthToken, Signer};
+use crate::token_store::{MemoryTokenStore, TokenKey, TokenStore, TokenStoreError};
+
+/// How long before expiry a cached token is replaced, by default.
+const DEFAULT_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// A cache of tokens keyed by host, port, user, region, credentials, and signing options.
+///
+/// [`Self::fetch_token`] returns the cached token for a signer's endpoint and
+/// user until it is within the refresh margin of its expiry, and only then
+/// generates a new one. One cache can serve several signers, e.g. one per
+/// tenant database. Tokens are kept in a [`MemoryTokenStore`] unless another
+/// [`TokenStore`] is passed to [`Self::with_store`].
+///
+/// ```rust,no_run
+/// # use aws_rds_signer::{Signer, TokenCache};
+/// # async fn connect(signer: Signer) -> Result<(), aws_rds_signer::Error> {
+/// let cache = TokenCache::new();
+/// // On every connect:
+/// let token = cache.fetch_token(&signer).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TokenCache<S = MemoryTokenStore> {
+    /// The store holding the cached tokens.
+    store: S,
+    /// How long before expiry a cached token is replaced.
+    refresh_margin: Duration,
+    /// Counters of cache hits, misses, and refreshes.
+    stats: StatsRecorder,
+    /// Locks held while a token is generated for a key, so concurrent
+    /// requests for that key wait for it instead of generating their own.
+    in_flight: Mutex<HashMap<TokenKey, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl Default for TokenCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenCache {
+    /// Creates an empty in-memory cache that replaces tokens one minute before they expire.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_store(MemoryTokenStore::new())
+    }
+
+    /// Bounds the number of cached tokens, evicting the least recently used token when full.
+    ///
+    /// Services that sign for many hosts and users, e.g. one per tenant,
+    /// otherwise keep a token for every combination they have seen until it
+    /// expires. A bound of zero is treated as one. Unbounded by default.
+    ///
+    /// # Arguments
+    /// * `max_entries` - The maximum number of cached tokens
+    #[must_use]
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.store = self.store.max_entries(max_entries);
+        self
+    }
+
+    /// Returns the number of cached tokens, including any that have expired since they were added.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.store.len()
+    }
+
+    /// Returns whether the cache holds no tokens.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
+}
+
+impl<S: TokenStore> TokenCache<S> {
+    /// Creates a cache keeping its tokens in `store`.
+    ///
+    /// # Arguments
+    /// * `store` - The storage backend, e.g. one shared with other processes
+    #[must_use]
+    pub fn with_store(store: S) -> Self {
+        Self {
+            store,
+            refresh_margin: DEFAULT_REFRESH_MARGIN,
+            stats: StatsRecorder::default(),
+            in_flight: Mutex::default(),
+        }
+    }
+
+    /// Sets how long before expiry a cached token is replaced.
+    ///
+    /// Tokens are only checked when a connection is opened, so the margin
+    /// should cover the time it takes to connect.
+    ///
+    /// # Arguments
+    /// * `refresh_margin` - The minimum remaining lifetime of a returned token
+    #[must_use]
+    pub const fn refresh_margin(mut self, refresh_margin: Duration) -> Self {
+        self.refresh_margin = refresh_margin;
+        self
+    }
+
+    /// Returns the store holding the cached tokens.
+    #[must_use]
+    pub const fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the cached token for `signer`'s endpoint and user, or generates one.
+    ///
+    /// The signer's credentials and region are resolved first to find its
+    /// [`TokenKey`]; the signer caches them until the credentials expire.
+    /// Concurrent requests for the same endpoint and user on an empty cache
+    /// wait for a single token instead of each generating one. Store failures
+    /// are logged and do not fail the request: a failed read is treated as a
+    /// miss, and a failed write still returns the new token.
+    ///
+    /// # Arguments
+    /// * `signer` - The signer generating tokens for the endpoint and user
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Signer::fetch_token`].
+    pub async fn fetch_token(&self, signer: &Signer) -> Result<AuthToken, crate::Error> {
+        let key = TokenKey::for_signer(signer).await?;
+        if let Some(token) = self.cached_token(&key).await {
+            self.stats.hit();
+            return Ok(token);
+        }
+
+        let lock = Arc::clone(
+            self.in_flight
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .entry(key.clone())
+                .or_default(),
+        );
+        let result = Box::pin(self.fetch_token_locked(signer, &key, &lock)).await;
+        let mut in_flight = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Only the map and this request hold the lock, so no other request is waiting on it.
+        if Arc::strong_count(&lock) <= 2 {
+            in_flight.remove(&key);
+        }
+        drop(in_flight);
+        result
+    }
+
+    /// Returns the cached token for `key` once `lock` is held, or generates one.
+    async fn fetch_token_locked(
+        &self,
+        signer: &Signer,
+        key: &TokenKey,
+        lock: &tokio::sync::Mutex<()>,
+    ) -> Result<AuthToken, crate::Error> {
+        let _generating = lock.lock().await;
+        // Another request may have cached a token while this one waited.
+        if let Some(token) = self.cached_token(key).await {
+            self.stats.hit();
+            return Ok(token);
+        }
+
+        self.stats.miss();
+        let token = Box::pin(signer.fetch_token()).await.inspect_err(|_| {
+            self.stats.refresh_failed();
+        })?;
+        self.stats.refreshed();
+        if let Err(e) = self.store.put(key, token.clone()).await {
+            tracing::warn!(error = %e, key = %key, "failed to cache an RDS token");
+        }
+        Ok(token)
+    }
+
+    /// Returns the token cached for `key` if it is not within the refresh margin of expiry.
+    async fn cached_token(&self, key: &TokenKey) -> Option<AuthToken> {
+        match self.store.get(key).await {
+            Ok(token) => {
+                token.filter(|token| token.refresh_after(self.refresh_margin) > SystemTime::now())
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, key = %key, "failed to read a cached RDS token");
+                None
+            }
+        }
+    }
+
+    /// Returns the cache's hit, miss, and refresh counters.
+    ///
+    /// They cover the requests made through this cache, not those of other
+    /// processes sharing its store.
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        self.stats.snapshot()
+    }
+
+    /// Generates and caches tokens for `signers` ahead of their first use, e.g. during startup.
+    ///
+    /// Tokens already cached and not within the refresh margin of expiry are
+    /// kept. With [`TokenCache::max_entries`] below the number of signers,
+    /// only the last tokens stay cached.
+    ///
+    /// # Arguments
+    /// * `signers` - The signers whose tokens are cached
+    ///
+    /// # Errors
+    /// Returns the first error of [`Signer::fetch_token`]; tokens generated before it stay cached.
+    pub async fn prefetch(&self, signers: &[Signer]) -> Result<(), crate::Error> {
+        for signer in signers {
+            Box::pin(self.fetch_token(signer)).await?;
+        }
+        Ok(())
+    }
+
+    /// Removes the cached token for `signer`'s endpoint and user, so the next request generates a new one.
+    ///
+    /// Call it after the database rejects a token, e.g. following an IAM
+    /// policy change, instead of waiting for the token to expire.
+    ///
+    /// # Arguments
+    /// * `signer` - The signer whose token is removed
+    ///
+    /// # Errors
+    /// * `TokenStoreError` - If the store fails to remove the token
+    ///
+    /// Otherwise returns the same errors as [`Signer::fetch_token`] when resolving credentials.
+    pub async fn invalidate(&self, signer: &Signer) -> Result<(), crate::Error> {
+        let key = TokenKey::for_signer(signer).await?;
+        self.store
+            .remove(&key)
+            .await
+            .map_err(|e| store_error(&format!("failed to remove the token for `{key}`"), &e))
+    }
+
+    /// Removes all cached tokens.
+    ///
+    /// # Errors
+    /// * `TokenStoreError` - If the store fails to remove the tokens
+    pub async fn invalidate_all(&self) -> Result<(), crate::Error> {
+        self.store
+            .clear()
+            .await
+            .map_err(|e| store_error("failed to remove the cached tokens", &e))
+    }
+}
+
+/// Converts a store failure into an error, prefixed with what failed.
+fn store_error(context: &str, error: &TokenStoreError) -> crate::Error {
+    crate::Error::TokenStoreError(format!("{context}: {error}"))
+}
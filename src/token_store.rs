@@ -0,0 +1,450 @@
+//! Storage backends for [`TokenCache`](crate::TokenCache).
+//!
+//! [`TokenStore`] holds the tokens a cache hands out. [`MemoryTokenStore`]
+//! keeps them in a map inside the process; other implementations can share
+//! tokens between processes, e.g. sidecars serving one fleet through an
+//! external cache, by storing [`AuthToken::value`] and restoring it with
+//! [`AuthToken::parse`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::future::{ready, Future};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{PoisonError, RwLock};
+use std::time::Duration;
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+
+use crate::sign::{AuthToken, Signer, DEFAULT_EXPIRES_IN, RDS_DB_SERVICE};
+
+/// The error type returned by a [`TokenStore`].
+pub type TokenStoreError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The future returned by [`TokenStore`] methods.
+pub type TokenStoreFuture<'a, T> =
+    Pin<Box<dyn Future<Output = Result<T, TokenStoreError>> + Send + 'a>>;
+
+/// Characters percent-encoded in the parts of a key's `Display` form: its
+/// separators, `%`, and whitespace and control characters.
+const KEY_ENCODE_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'%')
+    .add(b'/')
+    .add(b':')
+    .add(b';')
+    .add(b'=')
+    .add(b'@');
+
+/// The database endpoint, user, region, credentials, and signing options a cached token was signed for.
+///
+/// Signers that differ in any of them, e.g. only in the host clients dial,
+/// the profile their credentials come from, or in extra query parameters,
+/// get separate tokens.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TokenKey {
+    /// The RDS instance hostname.
+    host: String,
+    /// The host clients dial, if it differs from the signed host.
+    connect_host: Option<String>,
+    /// The database port.
+    port: u16,
+    /// The database user.
+    user: String,
+    /// The region tokens are signed for.
+    region: String,
+    /// The configured credential source, e.g. `profile:prod`, if any.
+    credential_source: Option<String>,
+    /// The access key ID of the resolved credentials, when no source or role identifies them.
+    access_key_id: Option<String>,
+    /// The ARNs of the roles assumed before signing, in order.
+    role_arns: Vec<String>,
+    /// The service name tokens are signed for.
+    service_name: String,
+    /// The lifetime of the tokens.
+    expires_in: Duration,
+    /// The additional query parameters added to tokens.
+    extra_params: Vec<(String, String)>,
+}
+
+impl TokenKey {
+    /// Returns the key of the tokens `signer` generates.
+    ///
+    /// The credentials and region are resolved as for signing, so signers
+    /// relying on the AWS configuration are keyed on the region and
+    /// credentials it resolves to.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Signer::fetch_token`] when resolving credentials.
+    pub(crate) async fn for_signer(signer: &Signer) -> Result<Self, crate::Error> {
+        let (credentials, region) = signer.resolve_for_signing().await?;
+        let credential_source = signer.credential_source_id();
+        let role_arns: Vec<String> = signer.role_arns().map(ToString::to_string).collect();
+        let access_key_id = (credential_source.is_none() && role_arns.is_empty())
+            .then(|| credentials.access_key_id().to_string());
+        Ok(Self {
+            host: signer.host().to_string(),
+            connect_host: (signer.connect_host() != signer.host())
+                .then(|| signer.connect_host().to_string()),
+            port: signer.port(),
+            user: signer.user().to_string(),
+            region,
+            credential_source,
+            access_key_id,
+            role_arns,
+            service_name: signer.service_name().to_string(),
+            expires_in: signer.expires_in(),
+            extra_params: signer.extra_params().to_vec(),
+        })
+    }
+
+    /// Returns the RDS instance hostname.
+    #[must_use]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns the database port.
+    #[must_use]
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns the database user.
+    #[must_use]
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Returns the region tokens are signed for.
+    #[must_use]
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+
+    /// Returns the host clients dial, or `None` if it is the signed host.
+    #[must_use]
+    pub fn connect_host(&self) -> Option<&str> {
+        self.connect_host.as_deref()
+    }
+
+    /// Returns the configured credential source, such as `profile:prod`,
+    /// `web_identity:<role ARN>`, `container:<URI>`, or `process:<command>`.
+    ///
+    /// Returns `None` for the default credential chain and custom providers.
+    #[must_use]
+    pub fn credential_source(&self) -> Option<&str> {
+        self.credential_source.as_deref()
+    }
+
+    /// Returns the access key ID of the signing credentials, when neither a
+    /// configured credential source nor an assumed role identifies them.
+    #[must_use]
+    pub fn access_key_id(&self) -> Option<&str> {
+        self.access_key_id.as_deref()
+    }
+
+    /// Returns the ARNs of the roles assumed before signing, in order.
+    #[must_use]
+    pub fn role_arns(&self) -> &[String] {
+        &self.role_arns
+    }
+
+    /// Returns the service name tokens are signed for.
+    #[must_use]
+    pub fn service_name(&self) -> &str {
+        &self.service_name
+    }
+
+    /// Returns the lifetime of the tokens.
+    #[must_use]
+    pub const fn expires_in(&self) -> Duration {
+        self.expires_in
+    }
+
+    /// Returns the additional query parameters added to tokens.
+    #[must_use]
+    pub fn extra_params(&self) -> &[(String, String)] {
+        &self.extra_params
+    }
+}
+
+/// Formats the key as `user@host:port/region`.
+///
+/// The credentials and options that differ from the defaults follow as
+/// `;name=value` pairs: `credential_source`, `access_key_id`, `role_arn` for
+/// each assumed role, `connect_host`, `service_name`, `expires_in` in seconds,
+/// and `param.<name>` for each extra query parameter. Separators, `%`, and
+/// whitespace within the parts are percent-encoded. Use it as the key in
+/// external stores.
+impl fmt::Display for TokenKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encode = |part: &str| utf8_percent_encode(part, KEY_ENCODE_SET).to_string();
+        write!(
+            f,
+            "{}@{}:{}/{}",
+            encode(&self.user),
+            encode(&self.host),
+            self.port,
+            encode(&self.region)
+        )?;
+        if let Some(credential_source) = &self.credential_source {
+            write!(f, ";credential_source={}", encode(credential_source))?;
+        }
+        if let Some(access_key_id) = &self.access_key_id {
+            write!(f, ";access_key_id={}", encode(access_key_id))?;
+        }
+        for role_arn in &self.role_arns {
+            write!(f, ";role_arn={}", encode(role_arn))?;
+        }
+        if let Some(connect_host) = &self.connect_host {
+            write!(f, ";connect_host={}", encode(connect_host))?;
+        }
+        if self.service_name != RDS_DB_SERVICE {
+            write!(f, ";service_name={}", encode(&self.service_name))?;
+        }
+        if self.expires_in != DEFAULT_EXPIRES_IN {
+            write!(f, ";expires_in={}", self.expires_in.as_secs())?;
+        }
+        for (name, value) in &self.extra_params {
+            write!(f, ";param.{}={}", encode(name), encode(value))?;
+        }
+        Ok(())
+    }
+}
+
+/// A storage backend for cached tokens.
+///
+/// Stores should keep a token no longer than [`AuthToken::expires_at`], e.g.
+/// by using it as the entry's expiry; the cache ignores tokens that are
+/// within its refresh margin of expiring.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use std::sync::Mutex;
+///
+/// use aws_rds_signer::{AuthToken, TokenKey, TokenStore, TokenStoreFuture};
+///
+/// #[derive(Debug, Default)]
+/// struct SharedStore(Mutex<HashMap<String, String>>);
+///
+/// impl TokenStore for SharedStore {
+///     fn get(&self, key: &TokenKey) -> TokenStoreFuture<'_, Option<AuthToken>> {
+///         let value = self.0.lock().unwrap().get(&key.to_string()).cloned();
+///         Box::pin(async move { Ok(value.map(|value| AuthToken::parse(&value)).transpose()?) })
+///     }
+///
+///     fn put(&self, key: &TokenKey, token: AuthToken) -> TokenStoreFuture<'_, ()> {
+///         self.0.lock().unwrap().insert(key.to_string(), token.to_string());
+///         Box::pin(async { Ok(()) })
+///     }
+///
+///     fn remove(&self, key: &TokenKey) -> TokenStoreFuture<'_, ()> {
+///         self.0.lock().unwrap().remove(&key.to_string());
+///         Box::pin(async { Ok(()) })
+///     }
+///
+///     fn clear(&self) -> TokenStoreFuture<'_, ()> {
+///         self.0.lock().unwrap().clear();
+///         Box::pin(async { Ok(()) })
+///     }
+/// }
+/// ```
+pub trait TokenStore: Send + Sync + fmt::Debug {
+    /// Returns the token stored for `key`, if any.
+    fn get(&self, key: &TokenKey) -> TokenStoreFuture<'_, Option<AuthToken>>;
+
+    /// Stores `token` for `key` until it expires, replacing any previous token.
+    fn put(&self, key: &TokenKey, token: AuthToken) -> TokenStoreFuture<'_, ()>;
+
+    /// Removes the token stored for `key`, if any.
+    fn remove(&self, key: &TokenKey) -> TokenStoreFuture<'_, ()>;
+
+    /// Removes all stored tokens.
+    fn clear(&self) -> TokenStoreFuture<'_, ()>;
+}
+
+/// The number of stored tokens below which expired tokens are not dropped.
+const MIN_SWEEP_LEN: usize = 64;
+
+/// A stored token and when it was last returned.
+#[derive(Debug)]
+struct Entry {
+    /// The stored token.
+    token: AuthToken,
+    /// The value of the store's use counter when the token was last returned.
+    last_used: AtomicU64,
+    /// The use counter value the token is filed under in the recency index.
+    indexed_at: u64,
+}
+
+/// The stored tokens and their order of use.
+#[derive(Debug, Default)]
+struct Tokens {
+    /// The stored tokens.
+    entries: HashMap<TokenKey, Entry>,
+    /// Every key filed under a use counter value, oldest first.
+    ///
+    /// Returning a token only updates its entry, so a key may have been used
+    /// since it was filed; eviction files such keys again before evicting.
+    recency: BTreeMap<u64, TokenKey>,
+    /// The number of stored tokens at which expired tokens are next dropped.
+    sweep_len: usize,
+}
+
+impl Tokens {
+    /// Removes the token stored for `key`, if any.
+    fn remove(&mut self, key: &TokenKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.indexed_at);
+        }
+    }
+
+    /// Drops expired tokens, and schedules the next sweep for when the store has doubled.
+    fn drop_expired(&mut self) {
+        let recency = &mut self.recency;
+        self.entries.retain(|_, entry| {
+            let expired = entry.token.is_expired();
+            if expired {
+                recency.remove(&entry.indexed_at);
+            }
+            !expired
+        });
+        self.sweep_len = self.entries.len().saturating_mul(2).max(MIN_SWEEP_LEN);
+    }
+
+    /// Evicts the least recently used token, returning whether one was evicted.
+    fn evict_lru(&mut self) -> bool {
+        while let Some((indexed_at, key)) = self.recency.pop_first() {
+            let Some(entry) = self.entries.get_mut(&key) else {
+                continue;
+            };
+            let last_used = *entry.last_used.get_mut();
+            if last_used == indexed_at {
+                self.entries.remove(&key);
+                return true;
+            }
+            entry.indexed_at = last_used;
+            self.recency.insert(last_used, key);
+        }
+        false
+    }
+}
+
+/// The default [`TokenStore`], a map inside the process.
+///
+/// Expired tokens are dropped when adding a token finds the store twice as
+/// large as after the previous sweep. With [`Self::max_entries`], the least
+/// recently used tokens are evicted once the bound is reached.
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    /// The stored tokens.
+    tokens: RwLock<Tokens>,
+    /// The maximum number of stored tokens, if bounded.
+    max_entries: Option<usize>,
+    /// Incremented whenever a token is stored or returned, to order entries by use.
+    uses: AtomicU64,
+}
+
+impl MemoryTokenStore {
+    /// Creates an empty, unbounded store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bounds the number of stored tokens, evicting the least recently used token when full.
+    ///
+    /// A bound of zero is treated as one.
+    ///
+    /// # Arguments
+    /// * `max_entries` - The maximum number of stored tokens
+    #[must_use]
+    pub const fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(if max_entries == 0 { 1 } else { max_entries });
+        self
+    }
+
+    /// Returns the number of stored tokens, including any that have expired since they were added.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tokens
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entries
+            .len()
+    }
+
+    /// Returns whether the store holds no tokens.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the unexpired token stored for `key`, marking it as used.
+    fn get_token(&self, key: &TokenKey) -> Option<AuthToken> {
+        self.tokens
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entries
+            .get(key)
+            .filter(|entry| !entry.token.is_expired())
+            .map(|entry| {
+                entry.last_used.store(self.next_use(), Ordering::Relaxed);
+                entry.token.clone()
+            })
+    }
+
+    /// Stores `token` for `key`, dropping expired tokens and evicting the least recently used if full.
+    fn put_token(&self, key: &TokenKey, token: AuthToken) {
+        let mut tokens = self.tokens.write().unwrap_or_else(PoisonError::into_inner);
+        tokens.remove(key);
+        if tokens.entries.len() >= tokens.sweep_len {
+            tokens.drop_expired();
+        }
+        if let Some(max_entries) = self.max_entries {
+            while tokens.entries.len() >= max_entries && tokens.evict_lru() {}
+        }
+        let used = self.next_use();
+        tokens.recency.insert(used, key.clone());
+        tokens.entries.insert(
+            key.clone(),
+            Entry {
+                token,
+                last_used: AtomicU64::new(used),
+                indexed_at: used,
+            },
+        );
+    }
+
+    /// Returns the next value of the use counter.
+    fn next_use(&self) -> u64 {
+        self.uses.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn get(&self, key: &TokenKey) -> TokenStoreFuture<'_, Option<AuthToken>> {
+        Box::pin(ready(Ok(self.get_token(key))))
+    }
+
+    fn put(&self, key: &TokenKey, token: AuthToken) -> TokenStoreFuture<'_, ()> {
+        self.put_token(key, token);
+        Box::pin(ready(Ok(())))
+    }
+
+    fn remove(&self, key: &TokenKey) -> TokenStoreFuture<'_, ()> {
+        self.tokens
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(key);
+        Box::pin(ready(Ok(())))
+    }
+
+    fn clear(&self) -> TokenStoreFuture<'_, ()> {
+        *self.tokens.write().unwrap_or_else(PoisonError::into_inner) = Tokens::default();
+        Box::pin(ready(Ok(())))
+    }
+}
@@ -0,0 +1,190 @@
+//! Validation of signer configuration.
+//!
+//! [`SignerBuilder::try_build`](crate::SignerBuilder::try_build) checks every
+//! field and reports all problems at once rather than stopping at the first.
+
+use std::fmt;
+use std::net::Ipv6Addr;
+use std::time::Duration;
+
+/// The longest lifetime RDS accepts for an authentication token.
+pub(crate) const MAX_EXPIRES_IN: Duration = Duration::from_secs(900);
+
+/// A signer configuration field that failed validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Field {
+    /// The RDS instance hostname.
+    Host,
+    /// The database port.
+    Port,
+    /// The database user.
+    User,
+    /// The AWS region.
+    Region,
+    /// The token lifetime.
+    ExpiresIn,
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Host => "host",
+            Self::Port => "port",
+            Self::User => "user",
+            Self::Region => "region",
+            Self::ExpiresIn => "expires_in",
+        })
+    }
+}
+
+/// A single validation failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// The offending field.
+    field: Field,
+    /// Why the field is invalid.
+    message: String,
+}
+
+impl FieldError {
+    /// Returns the offending field.
+    #[must_use]
+    pub const fn field(&self) -> Field {
+        self.field
+    }
+
+    /// Returns why the field is invalid.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Every field of a signer configuration that failed validation.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ValidationError {
+    /// The validation failures, in field order.
+    errors: Vec<FieldError>,
+}
+
+impl ValidationError {
+    /// Returns the validation failures.
+    #[must_use]
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    /// Records that `field` is invalid.
+    pub(crate) fn push(&mut self, field: Field, message: impl Into<String>) {
+        self.errors.push(FieldError {
+            field,
+            message: message.into(),
+        });
+    }
+
+    /// Returns `Err(self)` if any failure was recorded.
+    pub(crate) fn into_result(self) -> Result<(), Self> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid signer configuration: ")?;
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Returns whether `region` looks like an AWS region code (e.g., `us-east-1`, `us-gov-west-1`).
+pub(crate) fn is_region(region: &str) -> bool {
+    let parts: Vec<&str> = region.split('-').collect();
+    let Some((number, names)) = parts.split_last() else {
+        return false;
+    };
+    names.len() >= 2
+        && names
+            .iter()
+            .all(|name| !name.is_empty() && name.bytes().all(|b| b.is_ascii_lowercase()))
+        && !number.is_empty()
+        && number.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Removes the brackets around an IPv6 address such as `[fd00::1]`, leaving other hosts unchanged.
+pub(crate) fn unbracket_host(host: String) -> String {
+    host.strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .filter(|address| address.parse::<Ipv6Addr>().is_ok())
+        .map(str::to_string)
+        .unwrap_or(host)
+}
+
+/// Returns why `host` is not a bare DNS hostname or IP address, if it is not.
+///
+/// Hostnames consist of dot-separated labels of at most 63 letters, digits,
+/// and hyphens, without a leading or trailing hyphen, and are at most 253
+/// characters long.
+pub(crate) fn hostname_error(host: &str) -> Option<&'static str> {
+    if host.parse::<Ipv6Addr>().is_ok() {
+        return None;
+    }
+    if host.is_empty() {
+        return Some("must not be empty");
+    }
+    if host.contains("://") {
+        return Some("must not include a scheme");
+    }
+    if host.chars().any(char::is_whitespace) {
+        return Some("must not contain whitespace");
+    }
+    if let Some((_, port)) = host.rsplit_once(':') {
+        if port.parse::<u16>().map_or(true, |port| port == 0) {
+            return Some("has an invalid port (must be a number from 1 to 65535)");
+        }
+        return Some("must not include a port (use `port` or `host_and_port`)");
+    }
+    if host.contains('/') {
+        return Some("must not include a path");
+    }
+    if host.ends_with('.') {
+        return Some("must not end with a dot");
+    }
+    if host.len() > 253 {
+        return Some("must be at most 253 characters long");
+    }
+    for label in host.split('.') {
+        if label.is_empty() {
+            return Some("must not contain empty labels");
+        }
+        if label.len() > 63 {
+            return Some("labels must be at most 63 characters long");
+        }
+        if !label
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        {
+            return Some("must contain only letters, digits, hyphens, and dots");
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Some("labels must not start or end with a hyphen");
+        }
+    }
+    None
+}
@@ -0,0 +1,254 @@
+//! Offline inspection and verification of token signatures.
+//!
+//! [`AuthToken::signing_details`] rebuilds the canonical request, credential
+//! scope, and string-to-sign that a token's `SigV4` signature covers, for
+//! comparison with what AWS reports. [`verify`] recomputes the signature from
+//! the secret access key that should have signed the token and checks its
+//! host, user, credential scope, and lifetime, without contacting AWS. Together
+//! they narrow down "access denied" errors to a wrong key, user, host, or
+//! region, or an expired token, and let tests compare tokens against known
+//! keys.
+
+use std::time::SystemTime;
+
+use aws_sigv4::sign::v4;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use sha2::{Digest, Sha256};
+
+use crate::sign::{AuthToken, RDS_DB_SERVICE};
+use crate::validation::{self, MAX_EXPIRES_IN};
+
+/// The only signing algorithm RDS tokens use.
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// The SHA-256 digest of the empty payload that tokens are signed with.
+const EMPTY_PAYLOAD_HASH: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+/// Characters percent-encoded in the canonical query string: all but the unreserved set.
+const CANONICAL_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// The inputs to a token's `SigV4` signature, for debugging authentication failures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningDetails {
+    /// The canonical request: method, path, sorted query, `host` header, signed headers, and payload hash.
+    ///
+    /// It includes `X-Amz-Security-Token` for temporary credentials, so treat it as a secret.
+    pub canonical_request: String,
+    /// The credential scope, `date/region/service/aws4_request`.
+    pub credential_scope: String,
+    /// The string-to-sign: algorithm, timestamp, credential scope, and the canonical request's hash.
+    pub string_to_sign: String,
+}
+
+impl AuthToken {
+    /// Returns the canonical request, credential scope, and string-to-sign of the token's signature.
+    ///
+    /// They are rebuilt from the token's address and query parameters, so this
+    /// also works for tokens read with [`Self::parse`]. Compare them with the
+    /// canonical request and string-to-sign of a reference implementation,
+    /// such as the AWS CLI's `rds generate-db-auth-token` with `--debug`.
+    ///
+    /// # Errors
+    /// * `ParseError` - If the token has no query or is missing a signing parameter
+    pub fn signing_details(&self) -> Result<SigningDetails, crate::Error> {
+        let (address, _) = self
+            .value()
+            .split_once('?')
+            .ok_or_else(|| crate::Error::ParseError("invalid RDS token: missing query".into()))?;
+        let params = self.params();
+        let param = |name: &str| {
+            params
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+                .ok_or_else(|| {
+                    crate::Error::ParseError(format!(
+                        "invalid RDS token: missing `{name}` parameter"
+                    ))
+                })
+        };
+
+        let mut query = params
+            .iter()
+            .filter(|(name, _)| name != "X-Amz-Signature")
+            .map(|(name, value)| {
+                (
+                    utf8_percent_encode(name, CANONICAL_ENCODE_SET).to_string(),
+                    utf8_percent_encode(value, CANONICAL_ENCODE_SET).to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        query.sort();
+        let query = query
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("&");
+        // The default HTTPS port is omitted from the signed `host` header.
+        let authority = address.trim_end_matches('/');
+        let authority = authority.strip_suffix(":443").unwrap_or(authority);
+        let signed_headers = param("X-Amz-SignedHeaders")?;
+        let canonical_request =
+            format!("GET\n/\n{query}\nhost:{authority}\n\n{signed_headers}\n{EMPTY_PAYLOAD_HASH}");
+
+        let credential_scope = param("X-Amz-Credential")?
+            .split_once('/')
+            .map(|(_, scope)| scope.to_string())
+            .ok_or_else(|| {
+                crate::Error::ParseError(
+                    "invalid RDS token: `X-Amz-Credential` has no scope".into(),
+                )
+            })?;
+        let string_to_sign = format!(
+            "{}\n{}\n{credential_scope}\n{}",
+            param("X-Amz-Algorithm")?,
+            param("X-Amz-Date")?,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        Ok(SigningDetails {
+            canonical_request,
+            credential_scope,
+            string_to_sign,
+        })
+    }
+}
+
+/// Verifies `token` against `secret_key`, the host, and the user it should authenticate.
+///
+/// Equivalent to [`verify_at`] with the current time.
+///
+/// # Errors
+/// Returns the same errors as [`verify_at`].
+pub fn verify(
+    token: &str,
+    secret_key: &str,
+    expected_host: &str,
+    expected_user: &str,
+) -> Result<AuthToken, crate::Error> {
+    verify_at(
+        token,
+        secret_key,
+        expected_host,
+        expected_user,
+        SystemTime::now(),
+    )
+}
+
+/// Verifies `token` as of `now`, returning the parsed token if it is valid.
+///
+/// The token must be addressed to `expected_host` and `DBUser` must be
+/// `expected_user`. Its `X-Amz-Credential` scope must be for the day of
+/// `X-Amz-Date` and the `rds-db` service, its lifetime at most 15 minutes
+/// and not over at `now`, and its `X-Amz-Signature` must match the signature
+/// of its [`AuthToken::signing_details`] computed with `secret_key`. Pass a
+/// fixed `now` to check recorded tokens.
+///
+/// # Arguments
+/// * `token` - The token to verify
+/// * `secret_key` - The secret access key of the credentials that should have signed the token
+/// * `expected_host` - The database hostname the token should be for
+/// * `expected_user` - The database user the token should authenticate
+/// * `now` - The time to check the token's lifetime against
+///
+/// # Errors
+/// * `ParseError` - If the token is malformed or a signing parameter is missing or invalid
+/// * `VerificationError` - If a check fails; the message names the check
+pub fn verify_at(
+    token: &str,
+    secret_key: &str,
+    expected_host: &str,
+    expected_user: &str,
+    now: SystemTime,
+) -> Result<AuthToken, crate::Error> {
+    let parsed = AuthToken::parse(token)?;
+    let error = |reason: String| crate::Error::VerificationError(reason);
+
+    let expected_host = validation::unbracket_host(expected_host.to_string());
+    if !parsed.host().eq_ignore_ascii_case(&expected_host) {
+        return Err(error(format!(
+            "token is for host `{}`, expected `{expected_host}`",
+            parsed.host()
+        )));
+    }
+    if parsed.user() != expected_user {
+        return Err(error(format!(
+            "token is for user `{}`, expected `{expected_user}`",
+            parsed.user()
+        )));
+    }
+
+    let details = parsed.signing_details()?;
+    let params = parsed.params();
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map_or("", |(_, value)| value.as_str())
+    };
+    let algorithm = param("X-Amz-Algorithm");
+    if algorithm != ALGORITHM {
+        return Err(error(format!(
+            "unsupported signing algorithm `{algorithm}`"
+        )));
+    }
+    let signed_headers = param("X-Amz-SignedHeaders");
+    if signed_headers != "host" {
+        return Err(error(format!(
+            "signed headers are `{signed_headers}`, expected `host`"
+        )));
+    }
+    let [date, region, service, terminator] = details
+        .credential_scope
+        .split('/')
+        .collect::<Vec<_>>()
+        .try_into()
+        .map_err(|_| {
+            error(format!(
+                "malformed credential scope `{}`",
+                details.credential_scope
+            ))
+        })?;
+    if date.len() != 8 || !param("X-Amz-Date").starts_with(date) {
+        return Err(error(format!(
+            "credential scope date `{date}` does not match `X-Amz-Date`"
+        )));
+    }
+    if service != RDS_DB_SERVICE || terminator != "aws4_request" {
+        return Err(error(format!(
+            "credential scope is for `{service}/{terminator}`, expected `{RDS_DB_SERVICE}/aws4_request`"
+        )));
+    }
+
+    let expires_in = parsed
+        .expires_at()
+        .duration_since(parsed.signed_at())
+        .unwrap_or_default();
+    if expires_in > MAX_EXPIRES_IN {
+        return Err(error(format!(
+            "token lifetime {expires_in:?} exceeds the {MAX_EXPIRES_IN:?} RDS accepts"
+        )));
+    }
+    if parsed.expires_at() <= now {
+        return Err(error(format!(
+            "token expired at {}",
+            parsed.expires_at_rfc3339()
+        )));
+    }
+
+    let signing_key = v4::generate_signing_key(secret_key, parsed.signed_at(), region, service);
+    let signature = v4::calculate_signature(signing_key, details.string_to_sign.as_bytes());
+    if signature != param("X-Amz-Signature") {
+        let access_key = param("X-Amz-Credential")
+            .split('/')
+            .next()
+            .unwrap_or_default();
+        return Err(error(format!(
+            "signature does not match; the token was not signed with the secret key of `{access_key}`, or was modified"
+        )));
+    }
+    Ok(parsed)
+}